@@ -0,0 +1,261 @@
+use crate::cube::Cube;
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::scramble::Scramble;
+use crate::solvers::three::ThreeByThreeState;
+use crate::solvers::three::cubie::{CORNERS, CubieCube, EDGES};
+use crate::state::{CubeState, FACE_ORDER, Vector};
+use std::fmt;
+
+// The faces of each corner and edge position in Kociemba order, the face the
+// piece is oriented by first and the others clockwise.
+const CORNER_FACES: [[MoveFace; 3]; CORNERS] = [
+    [MoveFace::Up, MoveFace::Right, MoveFace::Front],
+    [MoveFace::Up, MoveFace::Front, MoveFace::Left],
+    [MoveFace::Up, MoveFace::Left, MoveFace::Back],
+    [MoveFace::Up, MoveFace::Back, MoveFace::Right],
+    [MoveFace::Down, MoveFace::Front, MoveFace::Right],
+    [MoveFace::Down, MoveFace::Left, MoveFace::Front],
+    [MoveFace::Down, MoveFace::Back, MoveFace::Left],
+    [MoveFace::Down, MoveFace::Right, MoveFace::Back],
+];
+const EDGE_FACES: [[MoveFace; 2]; EDGES] = [
+    [MoveFace::Up, MoveFace::Right],
+    [MoveFace::Up, MoveFace::Front],
+    [MoveFace::Up, MoveFace::Left],
+    [MoveFace::Up, MoveFace::Back],
+    [MoveFace::Down, MoveFace::Right],
+    [MoveFace::Down, MoveFace::Front],
+    [MoveFace::Down, MoveFace::Left],
+    [MoveFace::Down, MoveFace::Back],
+    [MoveFace::Front, MoveFace::Right],
+    [MoveFace::Front, MoveFace::Left],
+    [MoveFace::Back, MoveFace::Left],
+    [MoveFace::Back, MoveFace::Right],
+];
+
+/// How close to solved a 3x3 scramble leaves parts of the cube, judged
+/// against the centres wherever the scramble leaves them.
+///
+/// Colours are named by the face they are on when solved, and results for
+/// each colour or axis are in the order of `FACE_ORDER`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScrambleAnalysis {
+    /// Fewest moves to solve the cross on each colour.
+    pub cross_lengths: [usize; 6],
+    /// Edges that only quarter turns of the U and D, R and L, or F and B
+    /// faces can orient, by axis.
+    pub misoriented_edges: [usize; 3],
+    /// Corners of each colour joined to the edge they pair with in the first
+    /// two layers when that colour is the cross, wherever the pair is.
+    pub f2l_pairs: [usize; 6],
+    /// Corners not showing their U or D colour on U or D.
+    pub twisted_corners: usize,
+    /// 2x2x2 blocks solved around any of the corners.
+    pub solved_blocks: usize,
+}
+
+impl ScrambleAnalysis {
+    pub fn from_scramble(scramble: &Scramble) -> ScrambleAnalysis {
+        ScrambleAnalysis::from_state(&CubeState::from_scramble(Cube::ThreeByThree, scramble))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `state` is not a 3x3.
+    pub fn from_state(state: &CubeState) -> ScrambleAnalysis {
+        assert_eq!(state.size(), 3, "scramble analysis is only for the 3x3");
+        let pieces = pieces(state);
+        // The pieces as seen after turning the whole cube.
+        let turned = |rotation: Option<Move>| {
+            let mut state = state.clone();
+            state.apply_moves(rotation.as_slice());
+            cubie_cube(&state)
+        };
+        let cube = turned(None);
+
+        // `cube` names pieces by the faces their colours' centres are on, so
+        // each colour's cross is the cross of the face with its centre.
+        let cross_lengths = FACE_ORDER.map(|colour| {
+            let face = FACE_ORDER
+                .into_iter()
+                .find(|&face| state.sticker(face, 1, 1) == colour)
+                .expect("every colour has a centre");
+            ThreeByThreeState::from_cubie(cube).cross_length(face)
+        });
+        // Kociemba orientation is for the F and B axis, so each axis is turned
+        // to F first: D by x and R by y.
+        let misoriented = |cube: CubieCube| cube.eo.iter().filter(|&&eo| eo != 0).count();
+        let misoriented_edges = [
+            misoriented(turned(Some(rotation(MoveFace::Right, MoveType::Normal)))),
+            misoriented(turned(Some(rotation(MoveFace::Up, MoveType::Normal)))),
+            misoriented(cube),
+        ];
+        let f2l_pairs = FACE_ORDER.map(|colour| {
+            let has = |piece: &Piece, stickers: usize, with_colour: bool| {
+                piece.1.len() == stickers
+                    && piece.1.iter().any(|sticker| sticker.colour == colour) == with_colour
+            };
+            pieces
+                .iter()
+                .filter(|corner| has(corner, 3, true))
+                .filter(|corner| {
+                    pieces
+                        .iter()
+                        .any(|edge| has(edge, 2, false) && joined(corner, edge))
+                })
+                .count()
+        });
+
+        ScrambleAnalysis {
+            cross_lengths,
+            misoriented_edges,
+            f2l_pairs,
+            twisted_corners: cube.co.iter().filter(|&&co| co != 0).count(),
+            solved_blocks: (0..CORNERS)
+                .filter(|&corner| block_solved(&cube, corner))
+                .count(),
+        }
+    }
+
+    pub fn has_solved_blocks(&self) -> bool {
+        self.solved_blocks > 0
+    }
+}
+
+impl fmt::Display for ScrambleAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let by_colour = |values: &[usize; 6]| {
+            FACE_ORDER
+                .iter()
+                .zip(values)
+                .map(|(face, value)| format!("{face} {value}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let [ud, rl, fb] = self.misoriented_edges;
+        writeln!(f, "Cross: {}", by_colour(&self.cross_lengths))?;
+        writeln!(f, "Misoriented edges: U/D {ud}, R/L {rl}, F/B {fb}")?;
+        writeln!(f, "F2L pairs: {}", by_colour(&self.f2l_pairs))?;
+        writeln!(f, "Twisted corners: {}", self.twisted_corners)?;
+        write!(f, "Solved 2x2x2 blocks: {}", self.solved_blocks)
+    }
+}
+
+fn rotation(move_face: MoveFace, move_type: MoveType) -> Move {
+    Move {
+        move_face,
+        move_type,
+        move_width: MoveWidth::Rotation,
+    }
+}
+
+/// A sticker of a piece: the face it is on and its colour.
+#[derive(Debug, Clone, Copy)]
+struct Sticker {
+    face: MoveFace,
+    colour: MoveFace,
+}
+
+/// Where a piece is and its stickers.
+type Piece = (Vector, Vec<Sticker>);
+
+fn pieces(state: &CubeState) -> Vec<Piece> {
+    let mut pieces: Vec<Piece> = Vec::new();
+    for (f, &face) in FACE_ORDER.iter().enumerate() {
+        for (i, &colour) in state.face(face).iter().enumerate() {
+            let position = state.sticker_position(f * 9 + i).1;
+            let sticker = Sticker { face, colour };
+            match pieces.iter_mut().find(|(other, _)| *other == position) {
+                Some((_, stickers)) => stickers.push(sticker),
+                None => pieces.push((position, vec![sticker])),
+            }
+        }
+    }
+    pieces
+}
+
+/// Whether a corner and an edge sit side by side with both colours they
+/// share on the same faces, as on a solved cube.
+fn joined((corner_position, corner): &Piece, (edge_position, edge): &Piece) -> bool {
+    let apart: i32 = (0..3)
+        .map(|i| (corner_position[i] - edge_position[i]).abs())
+        .sum();
+    apart == 2
+        && edge.iter().all(|sticker| {
+            corner
+                .iter()
+                .any(|other| other.colour == sticker.colour && other.face == sticker.face)
+        })
+}
+
+/// The corners and edges of a 3x3, each identified by the faces of the
+/// centres matching its colours.
+fn cubie_cube(state: &CubeState) -> CubieCube {
+    let home = |colour: MoveFace| {
+        FACE_ORDER
+            .into_iter()
+            .find(|&face| state.sticker(face, 1, 1) == colour)
+            .expect("every colour has a centre")
+    };
+    let pieces = pieces(state);
+    // The faces the colours of the piece at the position with `faces` belong
+    // to, in the order of `faces`.
+    let homes = |faces: &[MoveFace]| -> Vec<MoveFace> {
+        let (_, stickers) = pieces
+            .iter()
+            .find(|(_, stickers)| {
+                stickers.len() == faces.len()
+                    && faces
+                        .iter()
+                        .all(|face| stickers.iter().any(|s| s.face == *face))
+            })
+            .expect("every position holds a piece");
+        faces
+            .iter()
+            .map(|face| {
+                let sticker = stickers.iter().find(|s| s.face == *face);
+                home(
+                    sticker
+                        .expect("the piece has a sticker on each face")
+                        .colour,
+                )
+            })
+            .collect()
+    };
+    let same_faces = |a: &[MoveFace], b: &[MoveFace]| a.iter().all(|face| b.contains(face));
+
+    let mut cube = CubieCube::SOLVED;
+    for (position, faces) in CORNER_FACES.iter().enumerate() {
+        let homes = homes(faces);
+        cube.cp[position] = CORNER_FACES
+            .iter()
+            .position(|corner| same_faces(corner, &homes))
+            .expect("corner colours match a corner") as u8;
+        cube.co[position] = homes
+            .iter()
+            .position(|face| matches!(face, MoveFace::Up | MoveFace::Down))
+            .expect("every corner has a U or D colour") as u8;
+    }
+    for (position, faces) in EDGE_FACES.iter().enumerate() {
+        let homes = homes(faces);
+        let edge = EDGE_FACES
+            .iter()
+            .position(|edge| same_faces(edge, &homes))
+            .expect("edge colours match an edge");
+        cube.ep[position] = edge as u8;
+        cube.eo[position] = (homes[0] != EDGE_FACES[edge][0]) as u8;
+    }
+    cube
+}
+
+/// Whether the corner at `corner` and the three edges next to it are solved.
+fn block_solved(cube: &CubieCube, corner: usize) -> bool {
+    let solved_edge = |edge: usize| cube.ep[edge] as usize == edge && cube.eo[edge] == 0;
+    cube.cp[corner] as usize == corner
+        && cube.co[corner] == 0
+        && EDGE_FACES
+            .iter()
+            .enumerate()
+            .filter(|(_, faces)| faces.iter().all(|face| CORNER_FACES[corner].contains(face)))
+            .all(|(edge, _)| solved_edge(edge))
+}
@@ -1,3 +1,6 @@
+use crate::error::ScrambleError;
+use std::fmt;
+
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub enum Cube {
     TwoByTwo,
@@ -6,4 +9,51 @@ pub enum Cube {
     FiveByFive,
     SixBySix,
     SevenBySeven,
+    /// Any size with the given number of layers. Sizes from 2 to 7 are
+    /// normally written with the named variants; see `Cube::from_layers`.
+    NxN(usize),
+}
+
+impl fmt::Display for Cube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{0}x{0}", self.layers())
+    }
+}
+
+impl Cube {
+    /// Number of layers along each edge of the cube.
+    pub fn layers(&self) -> usize {
+        match self {
+            Cube::TwoByTwo => 2,
+            Cube::ThreeByThree => 3,
+            Cube::FourByFour => 4,
+            Cube::FiveByFive => 5,
+            Cube::SixBySix => 6,
+            Cube::SevenBySeven => 7,
+            Cube::NxN(layers) => *layers,
+        }
+    }
+
+    /// The cube with `layers` layers, using the named variant where one exists.
+    pub fn from_layers(layers: usize) -> Cube {
+        match layers {
+            2 => Cube::TwoByTwo,
+            3 => Cube::ThreeByThree,
+            4 => Cube::FourByFour,
+            5 => Cube::FiveByFive,
+            6 => Cube::SixBySix,
+            7 => Cube::SevenBySeven,
+            layers => Cube::NxN(layers),
+        }
+    }
+}
+
+/// Rewrites `Cube::NxN` sizes that have a named variant, and rejects cubes
+/// with fewer than two layers.
+pub(crate) fn normalized(cube: Cube) -> Result<Cube, ScrambleError> {
+    match cube {
+        Cube::NxN(layers) if layers < 2 => Err(ScrambleError::InvalidCubeSize(layers)),
+        Cube::NxN(layers) => Ok(Cube::from_layers(layers)),
+        cube => Ok(cube),
+    }
 }
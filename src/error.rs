@@ -0,0 +1,81 @@
+use crate::cube::Cube;
+use crate::moves::Move;
+use std::error::Error;
+use std::fmt;
+
+/// Longest scramble the generators will produce.
+pub const MAX_SCRAMBLE_LENGTH: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScrambleError {
+    /// There is no random-state generator for this cube.
+    RandomStateUnsupported(Cube),
+    /// There is no blindfolded generator for this cube.
+    BlindUnsupported(Cube),
+    /// Cubes need at least two layers.
+    InvalidCubeSize(usize),
+    /// Scrambles must be between 1 and MAX_SCRAMBLE_LENGTH moves long.
+    InvalidLength(usize),
+    /// No state is this far from solved.
+    InvalidMinDepth { min_depth: usize, max_depth: usize },
+    /// Only moves on the same face and width can be combined.
+    CannotCombine(Move, Move),
+    /// Random scrambles do not turn up crosses this many moves from solved,
+    /// see `generators::cross::try_generate_with_rng`.
+    InvalidCrossLength {
+        cross_length: usize,
+        max_length: usize,
+    },
+    /// None of the scrambles tried had a cross of the wanted length.
+    CrossNotFound {
+        cross_length: usize,
+        attempts: usize,
+    },
+}
+
+impl fmt::Display for ScrambleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrambleError::RandomStateUnsupported(cube) => {
+                write!(f, "random-state scrambles are not supported for {cube}")
+            }
+            ScrambleError::BlindUnsupported(cube) => {
+                write!(f, "blindfolded scrambles are not supported for {cube}")
+            }
+            ScrambleError::InvalidCubeSize(layers) => {
+                write!(f, "a cube needs at least 2 layers, got {layers}")
+            }
+            ScrambleError::InvalidLength(length) => write!(
+                f,
+                "scramble length must be between 1 and {MAX_SCRAMBLE_LENGTH}, got {length}"
+            ),
+            ScrambleError::InvalidMinDepth {
+                min_depth,
+                max_depth,
+            } => write!(
+                f,
+                "minimum depth {min_depth} exceeds the maximum distance from solved of {max_depth}"
+            ),
+            ScrambleError::CannotCombine(first, second) => write!(
+                f,
+                "cannot combine {first} and {second}: moves are on different faces or widths"
+            ),
+            ScrambleError::InvalidCrossLength {
+                cross_length,
+                max_length,
+            } => write!(
+                f,
+                "cross length must be between 1 and {max_length} moves, got {cross_length}"
+            ),
+            ScrambleError::CrossNotFound {
+                cross_length,
+                attempts,
+            } => write!(
+                f,
+                "no scramble with a {cross_length} move cross found in {attempts} attempts"
+            ),
+        }
+    }
+}
+
+impl Error for ScrambleError {}
@@ -0,0 +1,88 @@
+use crate::cube::Cube;
+use crate::error::ScrambleError;
+use crate::generators::validator::is_canonical;
+use crate::generators::{five, four, three};
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::scramble::Scramble;
+use rand::Rng;
+
+/// Number of ways to hold a cube, and of orientation suffixes.
+pub const ORIENTATIONS: usize = 24;
+
+/// Scrambles for blindfolded solving: a normal scramble followed by a
+/// uniformly random orientation, so the solver cannot rely on holding the
+/// cube a fixed way. 3x3 scrambles are random-state; 4x4 and 5x5 scrambles
+/// are random-move.
+///
+/// The scramble and suffix together are canonical (see `is_canonical`): a
+/// 3x3 scramble is solved so that it does not end on the axis the suffix
+/// starts on, and a random-move scramble collapsing with the suffix is
+/// generated again, which keeps every orientation equally likely.
+pub fn try_generate_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    cube: Cube,
+) -> Result<Scramble, ScrambleError> {
+    if !matches!(
+        cube,
+        Cube::ThreeByThree | Cube::FourByFour | Cube::FiveByFive
+    ) {
+        return Err(ScrambleError::BlindUnsupported(cube));
+    }
+    let suffix = orientation(cube, rng.random_range(0..ORIENTATIONS));
+    let mut scramble = loop {
+        let scramble = match (cube, suffix.first()) {
+            (Cube::ThreeByThree, Some(first)) => {
+                three::generate_random_state_ending_off_axis(rng, first.move_face)
+            }
+            (Cube::ThreeByThree, None) => three::generate_random_state_with_rng(rng),
+            (Cube::FourByFour, _) => four::generate_with_rng(rng, None),
+            _ => five::generate_with_rng(rng, None),
+        };
+        let moves = [&scramble.moves[..], &suffix].concat();
+        if is_canonical(&moves, cube.layers()) {
+            break scramble;
+        }
+    };
+    scramble.moves.extend(suffix);
+    Ok(scramble)
+}
+
+/// The moves turning a cube to orientation `index`, in WCA style: at most
+/// one `Rw` or `Fw` move bringing a face to the top, then at most one `Uw`
+/// move. The moves turn half the cube, including the middle layer on odd
+/// cubes, so each one moves the centres the way a whole-cube rotation would.
+///
+/// # Panics
+///
+/// Panics if `index` is not below `ORIENTATIONS`.
+pub fn orientation(cube: Cube, index: usize) -> Vec<Move> {
+    assert!(
+        index < ORIENTATIONS,
+        "there are {ORIENTATIONS} orientations"
+    );
+    let move_width = MoveWidth::from_layers(cube.layers().div_ceil(2)).expect("a cube has layers");
+    let turn = |move_face, move_type| Move {
+        move_face,
+        move_type,
+        move_width,
+    };
+
+    let top = [
+        None,
+        Some(turn(MoveFace::Right, MoveType::Normal)),
+        Some(turn(MoveFace::Right, MoveType::Double)),
+        Some(turn(MoveFace::Right, MoveType::Prime)),
+        Some(turn(MoveFace::Front, MoveType::Normal)),
+        Some(turn(MoveFace::Front, MoveType::Prime)),
+    ];
+    let front = [
+        None,
+        Some(turn(MoveFace::Up, MoveType::Normal)),
+        Some(turn(MoveFace::Up, MoveType::Double)),
+        Some(turn(MoveFace::Up, MoveType::Prime)),
+    ];
+    [top[index / 4].clone(), front[index % 4].clone()]
+        .into_iter()
+        .flatten()
+        .collect()
+}
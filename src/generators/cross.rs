@@ -0,0 +1,97 @@
+use crate::error::ScrambleError;
+use crate::generators::three;
+use crate::moves::MoveFace;
+use crate::scramble::Scramble;
+use crate::solvers::cross::MAX_DEPTH;
+use crate::solvers::three::ThreeByThreeState;
+use crate::state::FACE_ORDER;
+use rand::Rng;
+
+/// Longest shortest cross over every colour that random scrambles turn up:
+/// seven moves come up about once in 4,000 random-state scrambles, while
+/// needing eight on every colour at once practically never happens.
+pub const MAX_ANY_COLOUR_DEPTH: usize = 7;
+
+/// Scrambles tried before giving up when no limit is given. Random-state
+/// crosses one move from solved turn up about once in 13,000 scrambles, so
+/// this is enough for every length from 1 to `MAX_DEPTH` on a chosen colour.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 100_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CrossOptions {
+    /// The colour whose cross is measured, named by its face when solved,
+    /// or `None` for whichever colour has the shortest cross.
+    pub colour: Option<MoveFace>,
+    /// Whether to try random-state scrambles rather than random-move ones,
+    /// which like the other generators are the default.
+    pub random_state: bool,
+    /// Scrambles to try before giving up.
+    pub max_attempts: usize,
+}
+
+impl Default for CrossOptions {
+    fn default() -> Self {
+        CrossOptions {
+            colour: None,
+            random_state: false,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+/// A 3x3 scramble whose optimal cross is exactly `cross_length` moves, found
+/// by generating scrambles until one has it. Only the accepted state is
+/// solved for a random-state scramble, so rejected attempts are cheap.
+///
+/// The length must be at least one move, as a cross already solved on a
+/// chosen colour turns up only once in 190,080 random-state scrambles, and
+/// at most `MAX_DEPTH`, or `MAX_ANY_COLOUR_DEPTH` without a colour.
+pub fn try_generate_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    cross_length: usize,
+    options: &CrossOptions,
+) -> Result<Scramble, ScrambleError> {
+    let max_length = match options.colour {
+        Some(_) => MAX_DEPTH,
+        None => MAX_ANY_COLOUR_DEPTH,
+    };
+    if !(1..=max_length).contains(&cross_length) {
+        return Err(ScrambleError::InvalidCrossLength {
+            cross_length,
+            max_length,
+        });
+    }
+
+    let fits = |state: &ThreeByThreeState| shortest_cross(state, options.colour) == cross_length;
+    for _ in 0..options.max_attempts {
+        if options.random_state {
+            let state = ThreeByThreeState::random(rng);
+            if fits(&state) {
+                return Ok(three::scramble_for(&state));
+            }
+        } else {
+            let scramble = three::generate_with_rng(rng, None);
+            let mut state = ThreeByThreeState::solved();
+            state.apply_moves(&scramble.moves);
+            if fits(&state) {
+                return Ok(scramble);
+            }
+        }
+    }
+    Err(ScrambleError::CrossNotFound {
+        cross_length,
+        attempts: options.max_attempts,
+    })
+}
+
+/// The cross length on `colour`, or the shortest over every colour.
+fn shortest_cross(state: &ThreeByThreeState, colour: Option<MoveFace>) -> usize {
+    match colour {
+        Some(colour) => state.cross_length(colour),
+        None => FACE_ORDER
+            .into_iter()
+            .map(|face| state.cross_length(face))
+            .min()
+            .expect("a cube has faces"),
+    }
+}
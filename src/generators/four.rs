@@ -1,15 +1,52 @@
-use crate::generators::validator::generate_validated_scramble;
-use crate::moves::MoveWidth;
+use crate::generators::validator::generate_canonical_scramble;
+use crate::moves::{Move, MoveFace, MoveWidth};
 use crate::scramble::Scramble;
+use crate::solvers::four::FourByFourState;
 use rand::{Rng, rng};
 
 pub fn generate(length: Option<usize>) -> Scramble {
-    let mut rng = rng();
-    let moves = generate_validated_scramble(&mut rng, length.unwrap_or(40), |rng| {
-        match rng.random_range(0..10) {
-            0..=7 => MoveWidth::Single, // 80% single moves
-            _ => MoveWidth::Wide,       // 20% wide moves
-        }
-    });
+    generate_with_rng(&mut rng(), length)
+}
+
+pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R, length: Option<usize>) -> Scramble {
+    let moves = generate_canonical_scramble(
+        rng,
+        4,
+        length.unwrap_or(40),
+        &[MoveWidth::Single, MoveWidth::Wide],
+    );
     Scramble { moves }
 }
+
+/// Picks a uniformly random state and returns the inverse of a reduction
+/// solution, which averages about 54 moves.
+///
+/// As in WCA scrambles, wide moves only turn R, U and F: `Lw` is written as
+/// `Rw` with the rest of the scramble rotated by `x'`. The cube ends in
+/// another orientation, which a 4x4 without fixed centres cannot tell apart.
+pub fn generate_random_state() -> Scramble {
+    generate_random_state_with_rng(&mut rng())
+}
+
+pub fn generate_random_state_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Scramble {
+    let state = FourByFourState::random(rng);
+    let mut moves: Vec<Move> = state.solve().iter().rev().map(Move::inverse).collect();
+    for i in 0..moves.len() {
+        let move_ = &moves[i];
+        if move_.move_width == MoveWidth::Wide
+            && [MoveFace::Left, MoveFace::Down, MoveFace::Back].contains(&move_.move_face)
+        {
+            let rotation = Move {
+                move_width: MoveWidth::Rotation,
+                ..move_.clone()
+            };
+            let rest = Scramble {
+                moves: moves.split_off(i + 1),
+            };
+            moves[i].move_face = moves[i].move_face.opposite();
+            moves.extend(rest.rotate(&[rotation]).moves);
+        }
+    }
+    // A move turned onto the opposite face can meet one already there.
+    Scramble { moves }.simplify()
+}
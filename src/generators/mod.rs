@@ -1,6 +1,10 @@
+pub mod blind;
+pub mod cross;
 pub mod five;
 pub mod four;
+pub mod nxn;
 pub mod seven;
 pub mod six;
 pub mod three;
+pub mod two;
 pub mod validator;
@@ -0,0 +1,36 @@
+use crate::error::MAX_SCRAMBLE_LENGTH;
+use crate::generators::validator::generate_canonical_scramble;
+use crate::moves::MoveWidth;
+use crate::scramble::Scramble;
+use rand::{Rng, rng};
+
+/// Default length for big cubes, extending the 40/60/80/100 moves used for
+/// 4x4 to 7x7 by twenty moves per extra layer, up to `MAX_SCRAMBLE_LENGTH`
+/// from 52x52 on.
+pub fn default_length(layers: usize) -> usize {
+    (20 * layers.saturating_sub(2)).min(MAX_SCRAMBLE_LENGTH)
+}
+
+pub fn generate(layers: usize, length: Option<usize>) -> Scramble {
+    generate_with_rng(&mut rng(), layers, length)
+}
+
+/// Generates a scramble for a cube with `layers` layers, turning between one
+/// and `layers / 2` layers at a time so the middle layer of odd cubes only
+/// moves with the outer ones.
+pub fn generate_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    layers: usize,
+    length: Option<usize>,
+) -> Scramble {
+    let widths: Vec<MoveWidth> = (1..=(layers / 2).max(1))
+        .filter_map(MoveWidth::from_layers)
+        .collect();
+    let moves = generate_canonical_scramble(
+        rng,
+        layers,
+        length.unwrap_or_else(|| default_length(layers)),
+        &widths,
+    );
+    Scramble { moves }
+}
@@ -1,16 +1,18 @@
-use crate::generators::validator::generate_validated_scramble;
+use crate::generators::validator::generate_canonical_scramble;
 use crate::moves::MoveWidth;
 use crate::scramble::Scramble;
 use rand::{Rng, rng};
 
 pub fn generate(length: Option<usize>) -> Scramble {
-    let mut rng = rng();
-    let moves = generate_validated_scramble(&mut rng, length.unwrap_or(80), |rng| {
-        match rng.random_range(0..10) {
-            0..=4 => MoveWidth::Single, // 50% single moves
-            5..=7 => MoveWidth::Wide,   // 30% wide moves
-            _ => MoveWidth::ThreeWide,  // 20% 3-wide moves
-        }
-    });
+    generate_with_rng(&mut rng(), length)
+}
+
+pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R, length: Option<usize>) -> Scramble {
+    let moves = generate_canonical_scramble(
+        rng,
+        6,
+        length.unwrap_or(80),
+        &[MoveWidth::Single, MoveWidth::Wide, MoveWidth::ThreeWide],
+    );
     Scramble { moves }
 }
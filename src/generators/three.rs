@@ -1,10 +1,102 @@
-use crate::generators::validator::generate_validated_scramble;
-use crate::moves::MoveWidth;
+use crate::generators::validator::{generate_canonical_scramble, has_cancellation, sort_blocks};
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
 use crate::scramble::Scramble;
-use rand::rng;
+use crate::solvers::three::{DEFAULT_MAX_LENGTH, ThreeByThreeState};
+use rand::{Rng, rng};
 
 pub fn generate(length: Option<usize>) -> Scramble {
-    let mut rng = rng();
-    let moves = generate_validated_scramble(&mut rng, length.unwrap_or(20), |_| MoveWidth::Single);
+    generate_with_rng(&mut rng(), length)
+}
+
+pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R, length: Option<usize>) -> Scramble {
+    let moves = generate_canonical_scramble(rng, 3, length.unwrap_or(20), &[MoveWidth::Single]);
     Scramble { moves }
 }
+
+/// Picks a uniformly random state and returns the inverse of a two-phase
+/// solution, which is typically 19 to 21 moves long.
+pub fn generate_random_state() -> Scramble {
+    generate_random_state_with_rng(&mut rng())
+}
+
+pub fn generate_random_state_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Scramble {
+    scramble_for(&ThreeByThreeState::random(rng))
+}
+
+/// The inverse of a two-phase solution to `state`, a scramble reaching it.
+pub(crate) fn scramble_for(state: &ThreeByThreeState) -> Scramble {
+    let solution = state
+        .solve(DEFAULT_MAX_LENGTH)
+        .expect("two-phase search always finds a 21 move solution");
+    reverse_solution(&solution)
+}
+
+/// Like `generate_random_state_with_rng`, but the scramble does not end on
+/// the axis of `face`, so that moves on that axis can follow it without
+/// collapsing.
+pub(crate) fn generate_random_state_ending_off_axis<R: Rng + ?Sized>(
+    rng: &mut R,
+    face: MoveFace,
+) -> Scramble {
+    let state = ThreeByThreeState::random(rng);
+    // The scramble ends with the inverse of the solution's first move.
+    let solution = (DEFAULT_MAX_LENGTH..)
+        .find_map(|max_length| state.solve_avoiding(max_length, Some(face), None))
+        .expect("every state has a solution");
+    reverse_solution(&solution)
+}
+
+/// The scramble undoing `solution`, in canonical order.
+fn reverse_solution(solution: &[Move]) -> Scramble {
+    let mut moves: Vec<Move> = solution.iter().rev().map(Move::inverse).collect();
+    sort_blocks(&mut moves);
+    Scramble { moves }
+}
+
+/// The moves a Fewest Moves scramble starts and ends with, `R' U' F`.
+pub const FMC_PADDING: [Move; 3] = [
+    Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Prime,
+        move_width: MoveWidth::Single,
+    },
+    Move {
+        move_face: MoveFace::Up,
+        move_type: MoveType::Prime,
+        move_width: MoveWidth::Single,
+    },
+    Move {
+        move_face: MoveFace::Front,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Single,
+    },
+];
+
+/// A Fewest Moves scramble as the WCA writes them: `R' U' F`, a random-state
+/// scramble, and `R' U' F` again. The middle never starts on the F and B
+/// axis or ends on the R and L axis, so nothing cancels with the padding.
+pub fn generate_fmc() -> Scramble {
+    generate_fmc_with_rng(&mut rng())
+}
+
+pub fn generate_fmc_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Scramble {
+    let [right, _, front] = &FMC_PADDING;
+    let state = ThreeByThreeState::random(rng);
+    // Avoiding an axis at each end rules out many short solutions, so allow
+    // a move more than usual to keep the search fast.
+    let solution = (DEFAULT_MAX_LENGTH + 1..)
+        .find_map(|max_length| {
+            state.solve_avoiding(max_length, Some(right.move_face), Some(front.move_face))
+        })
+        .expect("every state has a solution");
+
+    let mut moves = FMC_PADDING.to_vec();
+    moves.extend(solution.iter().rev().map(Move::inverse));
+    moves.extend(FMC_PADDING);
+    let scramble = Scramble { moves };
+    debug_assert!(
+        !has_cancellation(&scramble.moves),
+        "FMC scramble cancels with its padding: {scramble}"
+    );
+    scramble
+}
@@ -0,0 +1,54 @@
+use crate::error::ScrambleError;
+use crate::generators::validator::generate_canonical_scramble;
+use crate::moves::{Move, MoveWidth};
+use crate::scramble::Scramble;
+use crate::solvers::two::{DEFAULT_MIN_DEPTH, MAX_DEPTH, TwoByTwoState};
+use rand::{Rng, rng};
+
+pub fn generate(length: Option<usize>) -> Scramble {
+    generate_with_rng(&mut rng(), length)
+}
+
+pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R, length: Option<usize>) -> Scramble {
+    // On a 2x2 each of L, D and B turns the same cut as the opposite face,
+    // so only R, U and F are turned. That is enough to reach every state,
+    // and keeps the DBL corner fixed.
+    let moves = generate_canonical_scramble(rng, 2, length.unwrap_or(11), &[MoveWidth::Single]);
+    Scramble { moves }
+}
+
+/// Picks a uniformly random state at least `min_depth` moves from solved and
+/// returns the inverse of its optimal solution.
+pub fn generate_random_state(min_depth: Option<usize>) -> Scramble {
+    generate_random_state_with_rng(&mut rng(), min_depth)
+}
+
+pub fn generate_random_state_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    min_depth: Option<usize>,
+) -> Scramble {
+    try_generate_random_state_with_rng(rng, min_depth).unwrap_or_else(|error| panic!("{error}"))
+}
+
+pub fn try_generate_random_state_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    min_depth: Option<usize>,
+) -> Result<Scramble, ScrambleError> {
+    let min_depth = min_depth.unwrap_or(DEFAULT_MIN_DEPTH);
+    if min_depth > MAX_DEPTH {
+        return Err(ScrambleError::InvalidMinDepth {
+            min_depth,
+            max_depth: MAX_DEPTH,
+        });
+    }
+
+    let state = loop {
+        let state = TwoByTwoState::random(rng);
+        if state.distance() >= min_depth {
+            break state;
+        }
+    };
+
+    let moves = state.solve().iter().rev().map(Move::inverse).collect();
+    Ok(Scramble { moves })
+}
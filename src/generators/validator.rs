@@ -1,13 +1,21 @@
+use crate::error::ScrambleError;
 use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
 use rand::Rng;
+use rand::distr::{Distribution, weighted::WeightedIndex};
+use rand::seq::index;
 
 pub fn can_combine(first: &Move, second: &Move) -> bool {
-    first.move_face == second.move_face && first.move_width == second.move_width
+    first.move_face == second.move_face
+        && first.move_width.canonical() == second.move_width.canonical()
 }
 
 pub fn combine_moves(first: &Move, second: &Move) -> Option<Move> {
+    try_combine_moves(first, second).unwrap_or_else(|error| panic!("{error}"))
+}
+
+pub fn try_combine_moves(first: &Move, second: &Move) -> Result<Option<Move>, ScrambleError> {
     if !can_combine(first, second) {
-        panic!("Cannot combine moves on different faces or widths");
+        return Err(ScrambleError::CannotCombine(first.clone(), second.clone()));
     }
 
     let combined_type = match (first.move_type, second.move_type) {
@@ -22,66 +30,276 @@ pub fn combine_moves(first: &Move, second: &Move) -> Option<Move> {
         (MoveType::Prime, MoveType::Prime) => Some(MoveType::Double),
     };
 
-    combined_type.map(|move_type| Move {
+    Ok(combined_type.map(|move_type| Move {
         move_face: first.move_face,
         move_type,
-        move_width: first.move_width,
-    })
+        move_width: first.move_width.canonical(),
+    }))
+}
+
+/// Whether any moves in the sequence cancel or merge: neighbours on the
+/// same face and width, or the same face on both sides of a move on the
+/// opposite face.
+pub fn has_cancellation(moves: &[Move]) -> bool {
+    moves.windows(2).any(|pair| can_combine(&pair[0], &pair[1]))
+        || moves.windows(3).any(|triple| {
+            can_combine(&triple[0], &triple[2])
+                && triple[0].move_face.same_axis(&triple[1].move_face)
+        })
+}
+
+/// Puts each block of moves on one axis in canonical order (see
+/// `is_canonical`). Moves on one axis commute, so the sequence turns the
+/// same state.
+pub(crate) fn sort_blocks(moves: &mut [Move]) {
+    for block in moves.chunk_by_mut(|first, second| axis(first.move_face) == axis(second.move_face))
+    {
+        block.sort_by_key(slot_key);
+    }
+}
+
+/// Where a move goes in a block of moves on one axis in canonical order:
+/// faces in `MoveFace` order, then narrower widths first.
+fn slot_key(move_: &Move) -> (usize, (usize, usize)) {
+    let width = match move_.move_width {
+        MoveWidth::Slice(layer) => (1, layer),
+        MoveWidth::Middle => (2, 0),
+        MoveWidth::Rotation => (3, 0),
+        width => (
+            0,
+            width.layers().expect("the other widths turn outer layers"),
+        ),
+    };
+    (move_.move_face as usize, width)
+}
+
+fn axis(face: MoveFace) -> usize {
+    match face {
+        MoveFace::Left | MoveFace::Right => 0,
+        MoveFace::Up | MoveFace::Down => 1,
+        MoveFace::Front | MoveFace::Back => 2,
+    }
+}
+
+/// The cuts between neighbouring layers that a move turns across on a cube
+/// with `layers` layers. Cut `c` lies after the first `c` layers counted
+/// from the R, U or F face. Up to a whole-cube rotation, a move is the same
+/// as turning the layers on either side of its cuts the other way, so the
+/// cuts are all that matter about it.
+fn cuts(move_: &Move, layers: usize) -> Vec<usize> {
+    let turned = move_.move_width.turned_layers(layers);
+    let (start, end) = match move_.move_face {
+        MoveFace::Right | MoveFace::Up | MoveFace::Front => (turned.start, turned.end),
+        _ => (layers - turned.end, layers - turned.start),
+    };
+    [start, end]
+        .into_iter()
+        .filter(|cut| (1..layers).contains(cut))
+        .collect()
 }
 
-fn would_create_parallel_conflict(moves: &[Move], new_move: &Move) -> bool {
-    if moves.len() < 2 {
+/// Whether two moves on the same axis collapse on a cube with `layers`
+/// layers: together they are equivalent to fewer moves and a whole-cube
+/// rotation. That is the case when they turn across a common cut, e.g. `R R'`
+/// or `R 2R`, `Rw Lw` on a 4x4, or when either turns no cut at all, as a
+/// rotation or a move as wide as the cube does.
+pub fn collapses(first: &Move, second: &Move, layers: usize) -> bool {
+    if axis(first.move_face) != axis(second.move_face) {
         return false;
     }
+    let (first, second) = (cuts(first, layers), cuts(second, layers));
+    first.is_empty() || second.is_empty() || first.iter().any(|cut| second.contains(cut))
+}
 
-    let last_move = &moves[moves.len() - 1];
-    let second_last = &moves[moves.len() - 2];
+/// Whether the sequence is in the canonical form the generators produce on
+/// a cube with `layers` layers. Moves on one axis commute, so each maximal
+/// run of them is a block that could be written in any order. A sequence
+/// is canonical if no two moves in a block collapse (see `collapses`) and
+/// every block is in the order of faces in `MoveFace` and then narrower
+/// widths first, e.g. `L R Rw` but not `R L`, `R Rw R`, or `Rw Lw` on a 4x4.
+pub fn is_canonical(moves: &[Move], layers: usize) -> bool {
+    moves
+        .chunk_by(|first, second| axis(first.move_face) == axis(second.move_face))
+        .all(|block| {
+            block.is_sorted_by_key(slot_key)
+                && block.iter().enumerate().all(|(i, first)| {
+                    !cuts(first, layers).is_empty()
+                        && block[i + 1..]
+                            .iter()
+                            .all(|second| !collapses(first, second, layers))
+                })
+        })
+}
 
-    if second_last.move_face == new_move.move_face
-        && second_last.move_face.same_axis(&last_move.move_face)
-    {
-        return true;
+/// Number of canonical sequences of each length, as needed to sample them
+/// uniformly. The counts grow exponentially, so row `n` holds the counts
+/// divided by `e^logs[n]`, keeping every row near 1.
+struct Counts {
+    /// Counts for sequences not starting on each axis, then for all sequences.
+    rows: Vec<[f64; 4]>,
+    logs: Vec<f64>,
+}
+
+impl Counts {
+    /// `blocks[axis][k]` is the number of blocks of `k` moves on the axis.
+    fn new(length: usize, blocks: &[Vec<f64>; 3]) -> Counts {
+        let mut counts = Counts {
+            rows: vec![[1.0; 4]],
+            logs: vec![0.0],
+        };
+        for n in 1..=length {
+            let mut row = [0.0; 4];
+            for (before, count) in row.iter_mut().enumerate() {
+                *count = counts.weights(n, before, blocks).map(|(.., w)| w).sum();
+            }
+            let largest = row.iter().copied().fold(0.0, f64::max);
+            let base = counts.logs[n - 1];
+            if largest > 0.0 {
+                counts.rows.push(row.map(|count| count / largest));
+                counts.logs.push(base + largest.ln());
+            } else {
+                counts.rows.push(row);
+                counts.logs.push(base);
+            }
+        }
+        counts
     }
 
-    false
+    /// For the sequences of `length` moves not starting on axis `before`
+    /// (or any, if it is 3), how many start with each block: its axis, its
+    /// size and the count scaled by `e^logs[length - 1]`.
+    fn weights<'a>(
+        &'a self,
+        length: usize,
+        before: usize,
+        blocks: &'a [Vec<f64>; 3],
+    ) -> impl Iterator<Item = (usize, usize, f64)> + 'a {
+        (0..3)
+            .filter(move |&axis| axis != before)
+            .flat_map(move |axis| {
+                (1..blocks[axis].len().min(length + 1)).map(move |size| {
+                    let rest = length - size;
+                    let scale = (self.logs[rest] - self.logs[length - 1]).exp();
+                    (
+                        axis,
+                        size,
+                        blocks[axis][size] * self.rows[rest][axis] * scale,
+                    )
+                })
+            })
+    }
 }
 
-pub fn generate_validated_scramble<R: Rng>(
+/// Generates a random-move scramble of exactly `length` moves on all six
+/// faces, each turning one of `widths`. See
+/// `generate_canonical_scramble_with_faces`.
+pub fn generate_canonical_scramble<R: Rng + ?Sized>(
     rng: &mut R,
+    layers: usize,
     length: usize,
-    width_selector: impl Fn(&mut R) -> MoveWidth,
+    widths: &[MoveWidth],
 ) -> Vec<Move> {
-    let mut scramble_moves = Vec::new();
-
-    while scramble_moves.len() < length {
-        let move_face: MoveFace = rng.random();
-        let move_type: MoveType = rng.random();
-        let move_width = width_selector(rng);
+    generate_canonical_scramble_with_faces(
+        rng,
+        layers,
+        length,
+        &[
+            MoveFace::Left,
+            MoveFace::Right,
+            MoveFace::Up,
+            MoveFace::Down,
+            MoveFace::Front,
+            MoveFace::Back,
+        ],
+        widths,
+    )
+}
 
-        let new_move = Move {
-            move_face,
-            move_type,
-            move_width,
-        };
+/// Generates a random-move scramble of exactly `length` moves for a cube
+/// with `layers` layers, each turning one of `faces` at one of `widths`.
+/// Where two of those moves would turn across the same cut (see
+/// `collapses`), only the one on R, U or F is used, so on even cubes the
+/// widest moves only turn those faces and a 2x2 only turns R, U and F.
+///
+/// Every canonical sequence (see `is_canonical`) of that length over those
+/// moves is equally likely, up to floating-point rounding far below anything
+/// a test could measure. No moves are rejected or merged along the way, so
+/// no move is more likely for following a cancellation. The number of
+/// sequences starting with each possible block is counted first, then
+/// blocks are picked in proportion to how many sequences they start.
+///
+/// # Panics
+///
+/// Panics if there is no canonical sequence of that length, e.g. with a
+/// single face and width.
+pub fn generate_canonical_scramble_with_faces<R: Rng + ?Sized>(
+    rng: &mut R,
+    layers: usize,
+    length: usize,
+    faces: &[MoveFace],
+    widths: &[MoveWidth],
+) -> Vec<Move> {
+    let mut candidates: Vec<Move> = faces
+        .iter()
+        .flat_map(|&move_face| {
+            widths.iter().map(move |&move_width| Move {
+                move_face,
+                move_type: MoveType::Normal,
+                move_width,
+            })
+        })
+        .collect();
+    candidates.sort_by_key(|move_| {
+        let reference = matches!(
+            move_.move_face,
+            MoveFace::Right | MoveFace::Up | MoveFace::Front
+        );
+        (!reference, slot_key(move_))
+    });
 
-        if let Some(last_move) = scramble_moves.last()
-            && can_combine(last_move, &new_move)
+    let mut slots: [Vec<Move>; 3] = Default::default();
+    for candidate in candidates {
+        let axis_slots = &mut slots[axis(candidate.move_face)];
+        if axis_slots
+            .iter()
+            .all(|slot| !collapses(slot, &candidate, layers))
+            && !cuts(&candidate, layers).is_empty()
         {
-            if let Some(combined) = combine_moves(last_move, &new_move) {
-                scramble_moves.pop();
-                scramble_moves.push(combined);
-            } else {
-                scramble_moves.pop();
-            }
-            continue;
+            axis_slots.push(candidate);
         }
+    }
+    for axis_slots in &mut slots {
+        axis_slots.sort_by_key(slot_key);
+    }
 
-        if would_create_parallel_conflict(&scramble_moves, &new_move) {
-            continue;
+    // A block of `k` moves turns `k` of the axis' slots, each one of three
+    // ways.
+    let blocks = slots.each_ref().map(|axis_slots| {
+        let mut block_counts = vec![1.0];
+        for k in 1..=axis_slots.len() {
+            let previous = block_counts[k - 1];
+            block_counts.push(previous * (axis_slots.len() + 1 - k) as f64 / k as f64 * 3.0);
         }
+        block_counts
+    });
+    let counts = Counts::new(length, &blocks);
 
-        scramble_moves.push(new_move);
-    }
+    let mut moves = Vec::with_capacity(length);
+    let mut before = 3;
+    while moves.len() < length {
+        let remaining = length - moves.len();
+        let choices: Vec<_> = counts.weights(remaining, before, &blocks).collect();
+        let pick = WeightedIndex::new(choices.iter().map(|&(.., weight)| weight))
+            .unwrap_or_else(|_| panic!("no canonical scramble has {length} moves"));
+        let (axis, size, _) = choices[pick.sample(rng)];
 
-    scramble_moves
+        let mut picked = index::sample(rng, slots[axis].len(), size).into_vec();
+        picked.sort_unstable();
+        moves.extend(picked.into_iter().map(|slot| Move {
+            move_type: rng.random(),
+            ..slots[axis][slot].clone()
+        }));
+        before = axis;
+    }
+    moves
 }
@@ -1,8 +1,20 @@
+pub mod analysis;
 pub mod cube;
+pub mod error;
 pub mod generators;
 pub mod moves;
+pub mod notation;
+pub mod puzzles;
+pub mod render;
 pub mod scramble;
+pub mod solvers;
+pub mod state;
+pub mod transform;
 
+pub use analysis::ScrambleAnalysis;
 pub use cube::Cube;
+pub use error::ScrambleError;
 pub use moves::{Move, MoveFace, MoveType, MoveWidth};
+pub use notation::ParseError;
 pub use scramble::Scramble;
+pub use state::CubeState;
@@ -1,8 +1,19 @@
-use scramble_gen::{Cube, Scramble};
-use std::env;
+use rand::RngCore;
+use scramble_gen::generators::cross::CrossOptions;
+use scramble_gen::notation::NotationStyle;
+use scramble_gen::puzzles::clock::{ClockScramble, ClockState};
+use scramble_gen::puzzles::megaminx::MegaminxScramble;
+use scramble_gen::puzzles::pyraminx::PyraminxScramble;
+use scramble_gen::puzzles::skewb::SkewbScramble;
+use scramble_gen::puzzles::square1::Square1Scramble;
+use scramble_gen::render::{SvgOptions, render_clock_svg, render_svg};
+use scramble_gen::scramble::seeded_rng;
+use scramble_gen::{Cube, CubeState, Scramble, ScrambleAnalysis};
+use std::str::FromStr;
+use std::{env, fs, process};
 
 fn help() -> &'static str {
-    "scramble-gen - Generate practice scrambles for Rubik's cubes
+    "scramble-gen - Generate practice scrambles for Rubik's cubes and other twisty puzzles
 
 USAGE:
     scramble-gen [OPTIONS]
@@ -14,14 +25,53 @@ OPTIONS:
     --5x5                Generate 5x5 scrambles
     --6x6                Generate 6x6 scrambles
     --7x7                Generate 7x7 scrambles
+    --<n>x<n>            Generate scrambles for any bigger cube, e.g. --9x9
+    --pyram              Generate random-state Pyraminx scrambles
+    --skewb              Generate random-state Skewb scrambles
+    --minx               Generate Megaminx scrambles in Pochmann notation
+    --sq1                Generate random-state Square-1 scrambles
+    --clock              Generate random-state Rubik's Clock scrambles
     --length=<num>       Set scramble length (default varies by cube)
     --amount=<num>       Generate multiple scrambles (default: 1)
+    --random-state       Generate a random-state scramble (2x2, 3x3 and 4x4)
+    --blind              Generate a blindfolded scramble ending in a random orientation (3x3, 4x4 and 5x5)
+    --fmc                Generate a Fewest Moves scramble padded with R' U' F (3x3)
+    --cross=<num>        Generate a 3x3 scramble whose optimal cross is exactly <num> moves (1 to 8, or 7 on any colour)
+    --cross-colour=<f>   Colour of the cross, by its face: U, R, F, D, L or B (default: any colour)
+    --attempts=<num>     Scrambles to try for --cross before giving up (default: 100000)
+    --analyze            Report crosses, edge orientation, F2L pairs, twisted corners and solved blocks (3x3)
+    --notation=<style>   Notation for wide moves: wca (3Rw, default) or sign (3r)
+    --seed=<num>         Seed the generator to get reproducible scrambles
+    --draw=<file>        Write an SVG of the scrambled cube or clock (numbered per scramble with --amount)
     --help, -h           Show this help message
 
 EXAMPLES:
     scramble-gen --3x3
     scramble-gen --5x5 --amount=5
-    scramble-gen --7x7 --length=120"
+    scramble-gen --7x7 --length=120
+    scramble-gen --11x11
+    scramble-gen --pyram --amount=5
+    scramble-gen --skewb
+    scramble-gen --minx --amount=2
+    scramble-gen --sq1 --amount=5
+    scramble-gen --clock --draw=clock.svg
+    scramble-gen --2x2 --random-state
+    scramble-gen --3x3 --random-state --amount=5
+    scramble-gen --3x3 --blind --amount=5
+    scramble-gen --fmc --amount=3
+    scramble-gen --3x3 --random-state --analyze
+    scramble-gen --cross=5 --cross-colour=D --random-state --amount=5
+    scramble-gen --4x4 --draw=scramble.svg
+    scramble-gen --3x3 --amount=5 --seed=42"
+}
+
+enum Puzzle {
+    Cube(Cube),
+    Pyraminx,
+    Skewb,
+    Megaminx,
+    Square1,
+    Clock,
 }
 
 fn main() {
@@ -32,34 +82,200 @@ fn main() {
         return;
     }
 
-    let mut cube_type = Cube::ThreeByThree;
+    let mut puzzle = Puzzle::Cube(Cube::ThreeByThree);
     let mut length: Option<usize> = None;
     let mut amount = 1;
+    let mut random_state = false;
+    let mut blind = false;
+    let mut fmc = false;
+    let mut analyze = false;
+    let mut cross: Option<usize> = None;
+    let mut cross_options = CrossOptions::default();
+    let mut draw: Option<String> = None;
+    let mut seed: Option<u64> = None;
+    let mut notation = NotationStyle::Wca;
 
     for arg in &args[1..] {
         match arg.as_str() {
-            "--3x3" => cube_type = Cube::ThreeByThree,
-            "--4x4" => cube_type = Cube::FourByFour,
-            "--2x2" => cube_type = Cube::TwoByTwo,
-            "--5x5" => cube_type = Cube::FiveByFive,
-            "--6x6" => cube_type = Cube::SixBySix,
-            "--7x7" => cube_type = Cube::SevenBySeven,
+            "--3x3" => puzzle = Puzzle::Cube(Cube::ThreeByThree),
+            "--4x4" => puzzle = Puzzle::Cube(Cube::FourByFour),
+            "--2x2" => puzzle = Puzzle::Cube(Cube::TwoByTwo),
+            "--5x5" => puzzle = Puzzle::Cube(Cube::FiveByFive),
+            "--6x6" => puzzle = Puzzle::Cube(Cube::SixBySix),
+            "--7x7" => puzzle = Puzzle::Cube(Cube::SevenBySeven),
+            "--pyram" => puzzle = Puzzle::Pyraminx,
+            "--skewb" => puzzle = Puzzle::Skewb,
+            "--minx" => puzzle = Puzzle::Megaminx,
+            "--sq1" => puzzle = Puzzle::Square1,
+            "--clock" => puzzle = Puzzle::Clock,
+            "--random-state" => random_state = true,
+            "--blind" => blind = true,
+            "--fmc" => fmc = true,
+            "--analyze" => analyze = true,
             _ if arg.starts_with("--length=") => {
-                if let Some(len_str) = arg.strip_prefix("--length=") {
-                    length = len_str.parse().ok();
-                }
+                length = Some(flag_value(arg, "scramble length must be a number of moves"));
+            }
+            "--notation=wca" => notation = NotationStyle::Wca,
+            "--notation=sign" => notation = NotationStyle::Sign,
+            _ if arg.starts_with("--seed=") => {
+                seed = Some(flag_value(arg, "seed must be a whole number"));
+            }
+            _ if arg.starts_with("--draw=") => {
+                draw = arg.strip_prefix("--draw=").map(str::to_string);
+            }
+            _ if arg.starts_with("--cross=") => {
+                cross = Some(flag_value(arg, "cross length must be a number of moves"));
+            }
+            _ if arg.starts_with("--cross-colour=") => {
+                cross_options.colour = Some(flag_value(
+                    arg,
+                    "cross colour must be one of U, R, F, D, L or B",
+                ));
+            }
+            _ if arg.starts_with("--attempts=") => {
+                cross_options.max_attempts =
+                    flag_value(arg, "attempts must be a whole number of scrambles");
             }
             _ if arg.starts_with("--amount=") => {
-                if let Some(amt_str) = arg.strip_prefix("--amount=") {
-                    amount = amt_str.parse().unwrap_or(1);
+                amount = flag_value(arg, "amount must be a whole number of scrambles");
+            }
+            _ => {
+                if let Some(layers) = parse_size(arg) {
+                    puzzle = Puzzle::Cube(Cube::from_layers(layers));
+                }
+            }
+        }
+    }
+
+    // Each of these picks how the scramble is generated, except that cross
+    // scrambles can also start from a random state.
+    let modes: Vec<&str> = [
+        (fmc, "--fmc"),
+        (cross.is_some(), "--cross"),
+        (blind, "--blind"),
+        (random_state && cross.is_none(), "--random-state"),
+    ]
+    .into_iter()
+    .filter_map(|(given, flag)| given.then_some(flag))
+    .collect();
+    if modes.len() > 1 {
+        eprintln!("Error: {} cannot be combined", modes.join(" and "));
+        process::exit(1);
+    }
+    if fmc && !matches!(puzzle, Puzzle::Cube(Cube::ThreeByThree)) {
+        eprintln!("Error: FMC scrambles are only for the 3x3");
+        process::exit(1);
+    }
+    if cross.is_some() && !matches!(puzzle, Puzzle::Cube(Cube::ThreeByThree)) {
+        eprintln!("Error: cross scrambles are only for the 3x3");
+        process::exit(1);
+    }
+    cross_options.random_state = random_state;
+    if analyze && !matches!(puzzle, Puzzle::Cube(Cube::ThreeByThree)) {
+        eprintln!("Error: scramble analysis is only for the 3x3");
+        process::exit(1);
+    }
+
+    let mut rng: Box<dyn RngCore> = match seed {
+        Some(seed) => Box::new(seeded_rng(seed)),
+        None => Box::new(rand::rng()),
+    };
+
+    for i in 0..amount {
+        match puzzle {
+            Puzzle::Cube(cube) => {
+                let result = if fmc {
+                    Ok(Scramble::generate_fmc_with_rng(&mut rng))
+                } else if let Some(cross_length) = cross {
+                    Scramble::try_generate_cross_with_rng(cross_length, &cross_options, &mut rng)
+                } else if blind {
+                    Scramble::try_generate_blind_with_rng(cube, &mut rng)
+                } else if random_state {
+                    Scramble::try_generate_random_state_with_rng(cube, &mut rng)
+                } else {
+                    Scramble::try_generate_with_rng(cube, length, &mut rng)
+                };
+                let scramble = match result {
+                    Ok(scramble) => scramble,
+                    Err(e) => {
+                        eprintln!("Error: {e}");
+                        process::exit(1);
+                    }
+                };
+                println!("{}", scramble.to_notation(notation));
+                if analyze {
+                    println!("{}", ScrambleAnalysis::from_scramble(&scramble));
+                }
+
+                if let Some(path) = &draw {
+                    let state = CubeState::from_scramble(cube, &scramble);
+                    write_drawing(path, i, amount, &render_svg(&state, &SvgOptions::default()));
+                }
+            }
+            Puzzle::Pyraminx => println!("{}", PyraminxScramble::generate_with_rng(&mut rng)),
+            Puzzle::Skewb => println!("{}", SkewbScramble::generate_with_rng(&mut rng)),
+            Puzzle::Megaminx => {
+                // A blank line keeps multi-line scrambles apart.
+                if i > 0 {
+                    println!();
+                }
+                println!("{}", MegaminxScramble::generate_with_rng(&mut rng));
+            }
+            Puzzle::Square1 => println!("{}", Square1Scramble::generate_with_rng(&mut rng)),
+            Puzzle::Clock => {
+                let scramble = ClockScramble::generate_with_rng(&mut rng);
+                println!("{scramble}");
+
+                if let Some(path) = &draw {
+                    let mut state = ClockState::solved();
+                    state.apply_scramble(&scramble);
+                    let svg = render_clock_svg(&state, &SvgOptions::default());
+                    write_drawing(path, i, amount, &svg);
                 }
             }
-            _ => {}
         }
     }
+}
+
+/// Parses a size flag such as `--9x9` into its number of layers.
+fn parse_size(arg: &str) -> Option<usize> {
+    let (rows, cols) = arg.strip_prefix("--")?.split_once('x')?;
+    let layers = rows.parse().ok()?;
+    (cols.parse() == Ok(layers)).then_some(layers)
+}
+
+/// Parses the value of a `--flag=value` argument, or exits with an error
+/// saying what the value must be.
+fn flag_value<T: FromStr>(arg: &str, expected: &str) -> T {
+    let value = arg
+        .split_once('=')
+        .and_then(|(_, value)| value.parse().ok());
+    value.unwrap_or_else(|| {
+        eprintln!("Error: {expected}");
+        process::exit(1);
+    })
+}
 
-    for _ in 0..amount {
-        let scramble = Scramble::generate(cube_type, length);
-        println!("{scramble}");
+/// Writes the drawing of scramble `index`, numbering the file when there are
+/// several scrambles.
+fn write_drawing(path: &str, index: usize, amount: usize, svg: &str) {
+    let path = if amount > 1 {
+        numbered_path(path, index + 1)
+    } else {
+        path.to_string()
+    };
+    if let Err(e) = fs::write(&path, svg) {
+        eprintln!("Failed to write {path}: {e}");
+        process::exit(1);
+    }
+}
+
+/// Inserts `-<number>` before the extension, e.g. `cube.svg` becomes `cube-2.svg`.
+fn numbered_path(path: &str, number: usize) -> String {
+    match path.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && !extension.contains('/') => {
+            format!("{stem}-{number}.{extension}")
+        }
+        _ => format!("{path}-{number}"),
     }
 }
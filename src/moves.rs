@@ -3,6 +3,7 @@ use rand::{
     distr::{Distribution, StandardUniform},
 };
 use std::fmt;
+use std::ops::Range;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Move {
@@ -11,12 +12,73 @@ pub struct Move {
     pub move_width: MoveWidth,
 }
 
+impl Move {
+    pub fn inverse(&self) -> Move {
+        Move {
+            move_type: self.move_type.inverse(),
+            ..self.clone()
+        }
+    }
+}
+
+/// WCA notation (Regulations article 12a), e.g. `R'`, `Rw2` or `3Rw`, with
+/// `2R` for inner slices, `M`, `E` and `S` for middle layers and `x`, `y`
+/// and `z` for rotations.
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.move_width {
             MoveWidth::Single => write!(f, "{}{}", self.move_face, self.move_type),
             MoveWidth::Wide => write!(f, "{}w{}", self.move_face, self.move_type),
-            MoveWidth::ThreeWide => write!(f, "3{}{}", self.move_face, self.move_type),
+            MoveWidth::ThreeWide => write!(f, "3{}w{}", self.move_face, self.move_type),
+            MoveWidth::Layers(layers) => {
+                write!(f, "{layers}{}w{}", self.move_face, self.move_type)
+            }
+            MoveWidth::Slice(_) => {
+                write!(f, "{}{}{}", self.move_width, self.move_face, self.move_type)
+            }
+            MoveWidth::Middle | MoveWidth::Rotation => {
+                let (letter, follows) = self.axis_letter();
+                // Written from the other face, the move turns the other way.
+                let move_type = if self.move_face == follows {
+                    self.move_type
+                } else {
+                    self.move_type.inverse()
+                };
+                write!(f, "{letter}{move_type}")
+            }
+        }
+    }
+}
+
+impl Move {
+    /// The move written from the face its letter follows if it is a middle
+    /// layer or rotation, as parsing gives it, and with its width's named
+    /// variant, so that equal moves compare equal.
+    pub(crate) fn canonical(self) -> Move {
+        let move_width = self.move_width.canonical();
+        match move_width {
+            MoveWidth::Middle | MoveWidth::Rotation if self.move_face != self.axis_letter().1 => {
+                Move {
+                    move_face: self.move_face.opposite(),
+                    move_type: self.move_type.inverse(),
+                    move_width,
+                }
+            }
+            _ => Move { move_width, ..self },
+        }
+    }
+
+    /// The letter for a middle layer or rotation on this move's axis, and the
+    /// face it turns like.
+    fn axis_letter(&self) -> (char, MoveFace) {
+        let rotation = self.move_width == MoveWidth::Rotation;
+        match (self.move_face, rotation) {
+            (MoveFace::Left | MoveFace::Right, false) => ('M', MoveFace::Left),
+            (MoveFace::Up | MoveFace::Down, false) => ('E', MoveFace::Down),
+            (MoveFace::Front | MoveFace::Back, false) => ('S', MoveFace::Front),
+            (MoveFace::Left | MoveFace::Right, true) => ('x', MoveFace::Right),
+            (MoveFace::Up | MoveFace::Down, true) => ('y', MoveFace::Up),
+            (MoveFace::Front | MoveFace::Back, true) => ('z', MoveFace::Front),
         }
     }
 }
@@ -45,6 +107,17 @@ impl fmt::Display for MoveFace {
 }
 
 impl MoveFace {
+    pub fn opposite(&self) -> MoveFace {
+        match self {
+            MoveFace::Left => MoveFace::Right,
+            MoveFace::Right => MoveFace::Left,
+            MoveFace::Up => MoveFace::Down,
+            MoveFace::Down => MoveFace::Up,
+            MoveFace::Front => MoveFace::Back,
+            MoveFace::Back => MoveFace::Front,
+        }
+    }
+
     pub fn same_axis(&self, other: &MoveFace) -> bool {
         matches!(
             (self, other),
@@ -88,6 +161,16 @@ impl fmt::Display for MoveType {
     }
 }
 
+impl MoveType {
+    pub fn inverse(&self) -> MoveType {
+        match self {
+            MoveType::Double => MoveType::Double,
+            MoveType::Prime => MoveType::Normal,
+            MoveType::Normal => MoveType::Prime,
+        }
+    }
+}
+
 impl Distribution<MoveType> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> MoveType {
         match rng.random_range(0..3) {
@@ -103,6 +186,69 @@ pub enum MoveWidth {
     Single,
     Wide,
     ThreeWide,
+    /// Four or more layers, for cubes bigger than 7x7. Build widths with
+    /// `MoveWidth::from_layers` so that narrower moves use the named variants;
+    /// `MoveWidth::canonical` turns a narrower one into its named variant.
+    Layers(usize),
+    /// Only the given layer counted from the face, from 2 up, written as a
+    /// range of one layer, e.g. `2-2R`.
+    Slice(usize),
+    /// The middle layer, or the two middle layers of an even cube. `M` turns
+    /// like `L`, `E` like `D` and `S` like `F`.
+    Middle,
+    /// The whole cube. `x` turns like `R`, `y` like `U` and `z` like `F`.
+    Rotation,
+}
+
+impl MoveWidth {
+    /// The width turning `layers` layers, or `None` for zero, as a move
+    /// turns at least one layer.
+    pub fn from_layers(layers: usize) -> Option<MoveWidth> {
+        match layers {
+            0 => None,
+            1 => Some(MoveWidth::Single),
+            2 => Some(MoveWidth::Wide),
+            3 => Some(MoveWidth::ThreeWide),
+            layers => Some(MoveWidth::Layers(layers)),
+        }
+    }
+
+    /// The same width written with the named variant where there is one, so
+    /// that e.g. `Layers(2)` and `Wide` compare equal.
+    pub fn canonical(self) -> MoveWidth {
+        match self {
+            MoveWidth::Layers(1) | MoveWidth::Slice(1) => MoveWidth::Single,
+            MoveWidth::Layers(2) => MoveWidth::Wide,
+            MoveWidth::Layers(3) => MoveWidth::ThreeWide,
+            width => width,
+        }
+    }
+
+    /// Number of layers turned, counted from the named face, or `None` for
+    /// inner slices, middle layers and rotations, which do not start at the
+    /// face. `turned_layers` covers those too.
+    pub fn layers(&self) -> Option<usize> {
+        match self {
+            MoveWidth::Single => Some(1),
+            MoveWidth::Wide => Some(2),
+            MoveWidth::ThreeWide => Some(3),
+            MoveWidth::Layers(layers) => Some(*layers),
+            MoveWidth::Slice(_) | MoveWidth::Middle | MoveWidth::Rotation => None,
+        }
+    }
+
+    /// The layers turned on a cube with `size` layers, numbered from 0 at the
+    /// named face. Widths reaching past the cube are cut short, and `Slice(0)`
+    /// and `Layers(0)`, which name no layer, turn none.
+    pub fn turned_layers(&self, size: usize) -> Range<usize> {
+        let range = match self {
+            MoveWidth::Slice(layer) => layer.saturating_sub(1)..*layer,
+            MoveWidth::Middle => (size - 1) / 2..size - (size - 1) / 2,
+            MoveWidth::Rotation => 0..size,
+            width => 0..width.layers().expect("the other widths turn outer layers"),
+        };
+        range.start.min(size)..range.end.min(size)
+    }
 }
 
 impl Distribution<MoveWidth> for StandardUniform {
@@ -114,12 +260,17 @@ impl Distribution<MoveWidth> for StandardUniform {
     }
 }
 
+/// The width part of a move's WCA notation without the face, e.g. `3w` for
+/// `3Rw` or `2` for `2R`. Middle layers and rotations have their own letters
+/// instead of a face, so they have no width part.
 impl fmt::Display for MoveWidth {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            MoveWidth::Single => write!(f, ""),
+            MoveWidth::Single | MoveWidth::Middle | MoveWidth::Rotation => write!(f, ""),
             MoveWidth::Wide => write!(f, "w"),
-            MoveWidth::ThreeWide => write!(f, ""),
+            MoveWidth::ThreeWide => write!(f, "3w"),
+            MoveWidth::Layers(layers) => write!(f, "{layers}w"),
+            MoveWidth::Slice(layer) => write!(f, "{layer}-{layer}"),
         }
     }
 }
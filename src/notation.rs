@@ -0,0 +1,258 @@
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::scramble::Scramble;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum NotationStyle {
+    /// WCA Regulations article 12a: `R`, `Rw`, `3Rw`. This is what `Display`
+    /// produces.
+    #[default]
+    Wca,
+    /// SiGN: wide moves are written with a lowercase face, `R`, `r`, `3r`.
+    Sign,
+}
+
+impl Move {
+    pub fn to_notation(&self, style: NotationStyle) -> String {
+        match (style, self.move_width) {
+            (NotationStyle::Sign, MoveWidth::Wide) => {
+                format!(
+                    "{}{}",
+                    self.move_face.to_string().to_lowercase(),
+                    self.move_type
+                )
+            }
+            (NotationStyle::Sign, width @ (MoveWidth::ThreeWide | MoveWidth::Layers(_))) => {
+                format!(
+                    "{}{}{}",
+                    width.layers().expect("wide moves turn outer layers"),
+                    self.move_face.to_string().to_lowercase(),
+                    self.move_type
+                )
+            }
+            // Everything else is written the same in both styles.
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Scramble {
+    /// Formats the scramble like `Display`, with every move in `style`.
+    pub fn to_notation(&self, style: NotationStyle) -> String {
+        self.moves
+            .iter()
+            .map(|move_| format!("{} ", move_.to_notation(style)))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorKind {
+    /// A move token was expected but the input was empty.
+    Empty,
+    /// The character is not one of R, L, U, D, F or B, their lowercase SiGN
+    /// forms, a middle layer M, E or S, or a rotation x, y or z.
+    InvalidFace(char),
+    /// The layer count prefix does not describe a supported width.
+    UnsupportedWidth(usize),
+    /// Unexpected characters after the face, e.g. `R3` or `Rx`.
+    InvalidSuffix(String),
+}
+
+/// Error from parsing a move or scramble. `position` is the byte offset of the
+/// offending character in the parsed string.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Empty => write!(f, "expected a move at position {}", self.position),
+            ParseErrorKind::InvalidFace(c) => {
+                write!(f, "invalid face '{c}' at position {}", self.position)
+            }
+            ParseErrorKind::UnsupportedWidth(layers) => write!(
+                f,
+                "unsupported width of {layers} layers at position {}",
+                self.position
+            ),
+            ParseErrorKind::InvalidSuffix(suffix) => {
+                write!(
+                    f,
+                    "invalid suffix \"{suffix}\" at position {}",
+                    self.position
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl ParseError {
+    fn offset(self, by: usize) -> ParseError {
+        ParseError {
+            position: self.position + by,
+            ..self
+        }
+    }
+}
+
+/// The face named by `letter`, one of R, L, U, D, F or B.
+fn face(letter: char) -> Option<MoveFace> {
+    match letter {
+        'R' => Some(MoveFace::Right),
+        'L' => Some(MoveFace::Left),
+        'U' => Some(MoveFace::Up),
+        'D' => Some(MoveFace::Down),
+        'F' => Some(MoveFace::Front),
+        'B' => Some(MoveFace::Back),
+        _ => None,
+    }
+}
+
+impl FromStr for MoveFace {
+    type Err = ParseError;
+
+    /// Parses a single face letter such as `D`.
+    fn from_str(s: &str) -> Result<MoveFace, ParseError> {
+        let error = |position, kind| Err(ParseError { position, kind });
+        let Some(letter) = s.chars().next() else {
+            return error(0, ParseErrorKind::Empty);
+        };
+        let Some(move_face) = face(letter) else {
+            return error(0, ParseErrorKind::InvalidFace(letter));
+        };
+        match &s[letter.len_utf8()..] {
+            "" => Ok(move_face),
+            suffix => error(
+                letter.len_utf8(),
+                ParseErrorKind::InvalidSuffix(suffix.to_string()),
+            ),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    /// Parses a single move such as `R`, `U'`, `F2`, `Rw`, `Uw2` or `3Rw'`, the
+    /// SiGN forms `r`, `u2` and `3r'`, inner slices such as `2-2R` or `3-3R`,
+    /// middle layers `M`, `E` and `S`, or rotations `x`, `y` and `z`.
+    fn from_str(s: &str) -> Result<Move, ParseError> {
+        let error = |position, kind| Err(ParseError { position, kind });
+        if s.is_empty() {
+            return error(0, ParseErrorKind::Empty);
+        }
+
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        let layers = (digits > 0).then(|| s[..digits].parse().unwrap_or(usize::MAX));
+        // A range of one layer such as `2-2R` is an inner slice.
+        let (digits, range) = match (layers, s[digits..].strip_prefix('-')) {
+            (Some(layer), Some(rest)) => {
+                let to_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+                if rest[..to_digits].parse() != Ok(layer) {
+                    return error(0, ParseErrorKind::UnsupportedWidth(layer));
+                }
+                (digits + 1 + to_digits, true)
+            }
+            _ => (digits, false),
+        };
+
+        let Some(face_char) = s[digits..].chars().next() else {
+            return error(digits, ParseErrorKind::Empty);
+        };
+        let mut rest = &s[digits + face_char.len_utf8()..];
+        let axis_move = match face_char {
+            'M' => Some((MoveFace::Left, MoveWidth::Middle)),
+            'E' => Some((MoveFace::Down, MoveWidth::Middle)),
+            'S' => Some((MoveFace::Front, MoveWidth::Middle)),
+            'x' => Some((MoveFace::Right, MoveWidth::Rotation)),
+            'y' => Some((MoveFace::Up, MoveWidth::Rotation)),
+            'z' => Some((MoveFace::Front, MoveWidth::Rotation)),
+            _ => None,
+        };
+
+        let (move_face, move_width) = if let Some((move_face, move_width)) = axis_move {
+            if let Some(layers) = layers {
+                return error(0, ParseErrorKind::UnsupportedWidth(layers));
+            }
+            (move_face, move_width)
+        } else {
+            let Some(move_face) = face(face_char.to_ascii_uppercase()) else {
+                return error(digits, ParseErrorKind::InvalidFace(face_char));
+            };
+
+            let mut wide = face_char.is_ascii_lowercase();
+            if !wide && rest.starts_with('w') {
+                wide = true;
+                rest = &rest[1..];
+            }
+
+            // Inner slices are only written as ranges, so `3R` is still
+            // accepted as a three-layer move since earlier versions displayed
+            // them that way.
+            let move_width = match (layers, wide) {
+                (None, false) => MoveWidth::Single,
+                (None, true) => MoveWidth::Wide,
+                (Some(layer @ 2..), false) if range => MoveWidth::Slice(layer),
+                (Some(3), false) => MoveWidth::ThreeWide,
+                (Some(layers @ 2..), true) if !range => {
+                    MoveWidth::from_layers(layers).expect("there are two or more layers")
+                }
+                (Some(layers), _) => return error(0, ParseErrorKind::UnsupportedWidth(layers)),
+            };
+            (move_face, move_width)
+        };
+
+        let move_type = match rest {
+            "" => MoveType::Normal,
+            "'" => MoveType::Prime,
+            "2" | "2'" => MoveType::Double,
+            suffix => {
+                return error(
+                    s.len() - suffix.len(),
+                    ParseErrorKind::InvalidSuffix(suffix.to_string()),
+                );
+            }
+        };
+
+        Ok(Move {
+            move_face,
+            move_type,
+            move_width,
+        })
+    }
+}
+
+impl FromStr for Scramble {
+    type Err = ParseError;
+
+    /// Parses whitespace separated moves. Leading, trailing and repeated
+    /// whitespace is ignored.
+    fn from_str(s: &str) -> Result<Scramble, ParseError> {
+        let mut moves = Vec::new();
+        let mut start = None;
+
+        for (i, c) in s.char_indices().chain(std::iter::once((s.len(), ' '))) {
+            match (c.is_whitespace(), start) {
+                (false, None) => start = Some(i),
+                (true, Some(token_start)) => {
+                    let move_ = s[token_start..i]
+                        .parse()
+                        .map_err(|e: ParseError| e.offset(token_start))?;
+                    moves.push(move_);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Scramble { moves })
+    }
+}
@@ -0,0 +1,244 @@
+use crate::scramble::seeded_rng;
+use rand::{Rng, rng};
+use std::fmt;
+
+/// Which pins are up for a turn, named after the corners they sit at. Each
+/// raised pin joins the four dials around it on the face being turned.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClockPins {
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+    Up,
+    Right,
+    Down,
+    Left,
+    All,
+}
+
+impl fmt::Display for ClockPins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ClockPins::UpRight => "UR",
+            ClockPins::DownRight => "DR",
+            ClockPins::DownLeft => "DL",
+            ClockPins::UpLeft => "UL",
+            ClockPins::Up => "U",
+            ClockPins::Right => "R",
+            ClockPins::Down => "D",
+            ClockPins::Left => "L",
+            ClockPins::All => "ALL",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl ClockPins {
+    /// The corners with raised pins, as dial positions on the face being
+    /// turned.
+    fn corners(&self) -> &'static [usize] {
+        match self {
+            ClockPins::UpRight => &[2],
+            ClockPins::DownRight => &[8],
+            ClockPins::DownLeft => &[6],
+            ClockPins::UpLeft => &[0],
+            ClockPins::Up => &[0, 2],
+            ClockPins::Right => &[2, 8],
+            ClockPins::Down => &[6, 8],
+            ClockPins::Left => &[0, 6],
+            ClockPins::All => &[0, 2, 6, 8],
+        }
+    }
+}
+
+/// Pins turned before the clock is flipped, in WCA scramble order.
+pub const FRONT_PINS: [ClockPins; 9] = [
+    ClockPins::UpRight,
+    ClockPins::DownRight,
+    ClockPins::DownLeft,
+    ClockPins::UpLeft,
+    ClockPins::Up,
+    ClockPins::Right,
+    ClockPins::Down,
+    ClockPins::Left,
+    ClockPins::All,
+];
+/// Pins turned after the clock is flipped, in WCA scramble order.
+pub const BACK_PINS: [ClockPins; 5] = [
+    ClockPins::Up,
+    ClockPins::Right,
+    ClockPins::Down,
+    ClockPins::Left,
+    ClockPins::All,
+];
+
+/// A move in WCA notation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClockMove {
+    /// Turns the dials joined by the raised pins `amount` hours, clockwise
+    /// as seen from the face towards the solver. Written `UR3+` or `UR2-`.
+    Turn { pins: ClockPins, amount: i8 },
+    /// `y2`: turns the clock over, left to right.
+    Flip,
+}
+
+impl fmt::Display for ClockMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockMove::Turn { pins, amount } if *amount < 0 => write!(f, "{pins}{}-", -amount),
+            ClockMove::Turn { pins, amount } => write!(f, "{pins}{amount}+"),
+            ClockMove::Flip => write!(f, "y2"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClockScramble {
+    pub moves: Vec<ClockMove>,
+}
+
+impl fmt::Display for ClockScramble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for move_ in &self.moves {
+            write!(f, "{move_} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl ClockScramble {
+    pub fn generate() -> ClockScramble {
+        ClockScramble::generate_with_rng(&mut rng())
+    }
+
+    pub fn generate_seeded(seed: u64) -> ClockScramble {
+        ClockScramble::generate_with_rng(&mut seeded_rng(seed))
+    }
+
+    /// Turns each pin setting of the WCA sequence a random amount from 5
+    /// hours back to 6 forward. The fourteen turns move the fourteen dials
+    /// independently, so every state is equally likely.
+    pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R) -> ClockScramble {
+        let mut turn = |pins| ClockMove::Turn {
+            pins,
+            amount: rng.random_range(-5..=6),
+        };
+        let mut moves: Vec<ClockMove> = FRONT_PINS.into_iter().map(&mut turn).collect();
+        moves.push(ClockMove::Flip);
+        moves.extend(BACK_PINS.into_iter().map(&mut turn));
+        ClockScramble { moves }
+    }
+}
+
+/// Hours on a dial face.
+const HOURS: i8 = 12;
+
+/// State of a clock. Each face lists its nine dials in reading order as seen
+/// from that face, with 0 for twelve o'clock. A corner dial is the back of
+/// the corner beside it on the other face, so only fourteen dials move
+/// independently.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ClockState {
+    front: [u8; 9],
+    back: [u8; 9],
+    flipped: bool,
+}
+
+impl Default for ClockState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl fmt::Display for ClockState {
+    /// Writes the hour on each dial, front face on the left and back face
+    /// on the right, one row of dials per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hour = |dial: u8| if dial == 0 { HOURS as u8 } else { dial };
+        for row in 0..3 {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            let dials = |face: &[u8; 9]| {
+                face[row * 3..row * 3 + 3]
+                    .iter()
+                    .map(|&dial| format!("{:>2}", hour(dial)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            write!(f, "{}    {}", dials(&self.front), dials(&self.back))?;
+        }
+        Ok(())
+    }
+}
+
+impl ClockState {
+    pub fn solved() -> ClockState {
+        ClockState {
+            front: [0; 9],
+            back: [0; 9],
+            flipped: false,
+        }
+    }
+
+    /// Whether every dial points to twelve, whichever way round the clock is.
+    pub fn is_solved(&self) -> bool {
+        self.front.iter().chain(&self.back).all(|&dial| dial == 0)
+    }
+
+    /// The face towards the solver before the scramble.
+    pub fn front(&self) -> &[u8; 9] {
+        &self.front
+    }
+
+    pub fn back(&self) -> &[u8; 9] {
+        &self.back
+    }
+
+    /// Whether the back face is towards the solver.
+    pub fn is_flipped(&self) -> bool {
+        self.flipped
+    }
+
+    pub fn apply_move(&mut self, move_: &ClockMove) {
+        match *move_ {
+            ClockMove::Turn { pins, amount } => {
+                let (near, far) = if self.flipped {
+                    (&mut self.back, &mut self.front)
+                } else {
+                    (&mut self.front, &mut self.back)
+                };
+                let mut turned = [false; 9];
+                for &corner in pins.corners() {
+                    let (row, col) = (corner / 3, corner % 3);
+                    for r in [row, 1] {
+                        for c in [col, 1] {
+                            turned[r * 3 + c] = true;
+                        }
+                    }
+                    // Seen from the other side, the corner is mirrored and
+                    // turns the other way.
+                    let mirrored = row * 3 + 2 - col;
+                    far[mirrored] = turn_dial(far[mirrored], -amount);
+                }
+                for (dial, turned) in near.iter_mut().zip(turned) {
+                    if turned {
+                        *dial = turn_dial(*dial, amount);
+                    }
+                }
+            }
+            ClockMove::Flip => self.flipped = !self.flipped,
+        }
+    }
+
+    pub fn apply_scramble(&mut self, scramble: &ClockScramble) {
+        for move_ in &scramble.moves {
+            self.apply_move(move_);
+        }
+    }
+}
+
+fn turn_dial(dial: u8, amount: i8) -> u8 {
+    (dial as i8 + amount).rem_euclid(HOURS) as u8
+}
@@ -0,0 +1,119 @@
+use crate::scramble::seeded_rng;
+use rand::{Rng, rng};
+use std::fmt;
+
+/// Number of lines in a WCA Megaminx scramble.
+pub const LINES: usize = 7;
+/// Number of `R` and `D` moves on each line, before the closing `U` move.
+pub const LINE_LENGTH: usize = 10;
+
+/// A move in Pochmann notation. `R` and `D` turn everything but the left or
+/// top face by two fifths, upwards for `R++` and to the right for `D++`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MegaminxMove {
+    Right {
+        plus: bool,
+    },
+    Down {
+        plus: bool,
+    },
+    /// A fifth of a turn of the top face, clockwise unless `prime`.
+    Up {
+        prime: bool,
+    },
+}
+
+impl fmt::Display for MegaminxMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = |plus: bool| if plus { "++" } else { "--" };
+        match self {
+            MegaminxMove::Right { plus } => write!(f, "R{}", sign(*plus)),
+            MegaminxMove::Down { plus } => write!(f, "D{}", sign(*plus)),
+            MegaminxMove::Up { prime: false } => write!(f, "U"),
+            MegaminxMove::Up { prime: true } => write!(f, "U'"),
+        }
+    }
+}
+
+impl MegaminxMove {
+    pub fn inverse(&self) -> MegaminxMove {
+        match *self {
+            MegaminxMove::Right { plus } => MegaminxMove::Right { plus: !plus },
+            MegaminxMove::Down { plus } => MegaminxMove::Down { plus: !plus },
+            MegaminxMove::Up { prime } => MegaminxMove::Up { prime: !prime },
+        }
+    }
+}
+
+/// A scramble kept as the lines it is written on, since competitors read
+/// Megaminx scrambles line by line.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MegaminxScramble {
+    pub lines: Vec<Vec<MegaminxMove>>,
+}
+
+impl fmt::Display for MegaminxScramble {
+    /// Writes each line on its own, with the moves separated by spaces.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.to_lines().iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl MegaminxScramble {
+    pub fn generate() -> MegaminxScramble {
+        MegaminxScramble::generate_with_rng(&mut rng())
+    }
+
+    pub fn generate_seeded(seed: u64) -> MegaminxScramble {
+        MegaminxScramble::generate_with_rng(&mut seeded_rng(seed))
+    }
+
+    /// Each line alternates `R` and `D` moves of random direction, starting
+    /// with `R`, and ends with `U` if the last `D` was `D++` or `U'` if it
+    /// was `D--`.
+    pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R) -> MegaminxScramble {
+        let lines = (0..LINES)
+            .map(|_| {
+                let mut line: Vec<MegaminxMove> = (0..LINE_LENGTH)
+                    .map(|i| {
+                        let plus = rng.random_bool(0.5);
+                        if i % 2 == 0 {
+                            MegaminxMove::Right { plus }
+                        } else {
+                            MegaminxMove::Down { plus }
+                        }
+                    })
+                    .collect();
+                let prime = line.last() == Some(&MegaminxMove::Down { plus: false });
+                line.push(MegaminxMove::Up { prime });
+                line
+            })
+            .collect();
+        MegaminxScramble { lines }
+    }
+
+    /// The moves in order, ignoring the line breaks.
+    pub fn moves(&self) -> impl Iterator<Item = &MegaminxMove> {
+        self.lines.iter().flatten()
+    }
+
+    /// Each line formatted on its own, for showing the scramble one line per
+    /// row.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(MegaminxMove::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+}
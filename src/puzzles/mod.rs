@@ -0,0 +1,5 @@
+pub mod clock;
+pub mod megaminx;
+pub mod pyraminx;
+pub mod skewb;
+pub mod square1;
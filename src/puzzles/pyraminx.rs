@@ -0,0 +1,502 @@
+use crate::scramble::seeded_rng;
+use rand::{Rng, rng};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// The four corners of the Pyraminx. Each face is numbered after the corner
+/// opposite it, so face 0 is the bottom face and face 3 the front face.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PyraminxAxis {
+    Up,
+    Left,
+    Right,
+    Back,
+}
+
+const AXES: [PyraminxAxis; 4] = [
+    PyraminxAxis::Up,
+    PyraminxAxis::Left,
+    PyraminxAxis::Right,
+    PyraminxAxis::Back,
+];
+
+impl PyraminxAxis {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for PyraminxAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PyraminxAxis::Up => write!(f, "U"),
+            PyraminxAxis::Left => write!(f, "L"),
+            PyraminxAxis::Right => write!(f, "R"),
+            PyraminxAxis::Back => write!(f, "B"),
+        }
+    }
+}
+
+/// A 120 degree turn around a corner, clockwise when looking at that corner.
+/// Tip moves only turn the corner's tip and are written in lowercase.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PyraminxMove {
+    pub axis: PyraminxAxis,
+    pub tip: bool,
+    pub prime: bool,
+}
+
+impl fmt::Display for PyraminxMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tip {
+            write!(f, "{}", self.axis.to_string().to_lowercase())?;
+        } else {
+            write!(f, "{}", self.axis)?;
+        }
+        if self.prime {
+            write!(f, "'")?;
+        }
+        Ok(())
+    }
+}
+
+impl PyraminxMove {
+    pub fn inverse(&self) -> PyraminxMove {
+        PyraminxMove {
+            prime: !self.prime,
+            ..*self
+        }
+    }
+}
+
+/// Random-state scrambles need at least this many non-tip moves to solve.
+pub const DEFAULT_MIN_DEPTH: usize = 6;
+/// Every state can be solved in at most this many non-tip moves.
+pub const MAX_DEPTH: usize = 11;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PyraminxScramble {
+    pub moves: Vec<PyraminxMove>,
+}
+
+impl fmt::Display for PyraminxScramble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for move_ in &self.moves {
+            write!(f, "{move_} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl PyraminxScramble {
+    pub fn generate() -> PyraminxScramble {
+        PyraminxScramble::generate_with_rng(&mut rng())
+    }
+
+    pub fn generate_seeded(seed: u64) -> PyraminxScramble {
+        PyraminxScramble::generate_with_rng(&mut seeded_rng(seed))
+    }
+
+    /// Picks a state uniformly from the 933,120 tipless states at least
+    /// DEFAULT_MIN_DEPTH moves from solved, solves it optimally and inverts
+    /// the solution, then appends each tip turned randomly.
+    pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R) -> PyraminxScramble {
+        let tables = tables();
+        let state = loop {
+            let index = tables.reachable[rng.random_range(0..tables.reachable.len())] as usize;
+            if tables.distance[index] as usize >= DEFAULT_MIN_DEPTH {
+                break PyraminxState::from_index(index);
+            }
+        };
+
+        // The tips of `state` are solved but the big turns move them, so the
+        // tip moves of the solution are dropped in favour of random ones.
+        let mut moves: Vec<PyraminxMove> = state
+            .solve()
+            .iter()
+            .filter(|move_| !move_.tip)
+            .rev()
+            .map(PyraminxMove::inverse)
+            .collect();
+        for axis in AXES {
+            match rng.random_range(0..3) {
+                0 => {}
+                turn => moves.push(PyraminxMove {
+                    axis,
+                    tip: true,
+                    prime: turn == 2,
+                }),
+            }
+        }
+        PyraminxScramble { moves }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Piece {
+    Tip,
+    Centre,
+    Edge,
+}
+
+const STICKERS: usize = 36;
+
+/// The three corners of a face, in increasing order.
+fn face_corners(face: usize) -> [usize; 3] {
+    let mut corners = [0; 3];
+    let mut next = 0;
+    for corner in 0..4 {
+        if corner != face {
+            corners[next] = corner;
+            next += 1;
+        }
+    }
+    corners
+}
+
+/// Index of the sticker on `face` belonging to a piece. Tips and centres are
+/// named by their corner `a`, edges by the corners `a` and `b` they join.
+fn sticker(face: usize, piece: Piece, a: usize, b: usize) -> usize {
+    let corners = face_corners(face);
+    let slot = |corner| {
+        corners
+            .iter()
+            .position(|&c| c == corner)
+            .expect("corner is on face")
+    };
+    let offset = match piece {
+        Piece::Tip => slot(a),
+        Piece::Centre => 3 + slot(a),
+        Piece::Edge => 6 + slot(a) + slot(b) - 1,
+    };
+    face * 9 + offset
+}
+
+// Where each other corner goes under a clockwise turn around the indexed one.
+const CORNER_CYCLES: [[usize; 4]; 4] = [[0, 3, 1, 2], [2, 1, 3, 0], [3, 0, 2, 1], [1, 2, 0, 3]];
+
+const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+const EDGE_STATES: usize = 720 * 64;
+const CENTRE_STATES: usize = 81;
+
+/// Sticker-level state of a Pyraminx. Each sticker holds the face it belongs
+/// to on a solved puzzle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PyraminxState {
+    stickers: [u8; STICKERS],
+}
+
+impl Default for PyraminxState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl PyraminxState {
+    pub fn solved() -> PyraminxState {
+        PyraminxState {
+            stickers: std::array::from_fn(|i| (i / 9) as u8),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == PyraminxState::solved()
+    }
+
+    /// Colour of a sticker: the face it was on when solved.
+    pub fn stickers(&self) -> &[u8; STICKERS] {
+        &self.stickers
+    }
+
+    pub fn apply_move(&mut self, move_: &PyraminxMove) {
+        let turn = &tables().turns[move_.axis.index() * 2 + move_.tip as usize];
+        permute(&mut self.stickers, turn, if move_.prime { 2 } else { 1 });
+    }
+
+    pub fn apply_scramble(&mut self, scramble: &PyraminxScramble) {
+        for move_ in &scramble.moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Number of non-tip moves in an optimal solution, ignoring the tips.
+    pub fn distance(&self) -> usize {
+        tables().distance[self.index()] as usize
+    }
+
+    /// An optimal solution of the non-tip pieces followed by the tip moves
+    /// still needed.
+    pub fn solve(&self) -> Vec<PyraminxMove> {
+        let tables = tables();
+        let mut solution = Vec::new();
+        let mut edges = self.edge_coordinate();
+        let mut centres = self.centre_coordinate(Piece::Centre);
+
+        loop {
+            let distance = tables.distance[edges * CENTRE_STATES + centres];
+            if distance == 0 {
+                break;
+            }
+            let (m, next_edges, next_centres) = (0..8)
+                .map(|m| {
+                    (
+                        m,
+                        tables.edge_moves[edges][m] as usize,
+                        tables.centre_moves[centres][m] as usize,
+                    )
+                })
+                .find(|&(_, e, c)| tables.distance[e * CENTRE_STATES + c] < distance)
+                .expect("every unsolved state has a move towards solved");
+            solution.push(PyraminxMove {
+                axis: AXES[m / 2],
+                tip: false,
+                prime: m % 2 == 1,
+            });
+            edges = next_edges;
+            centres = next_centres;
+        }
+
+        // Turns of the corners also turn their tips, so the tips are solved
+        // from wherever the rest of the solution leaves them.
+        let mut solved = *self;
+        for move_ in &solution {
+            solved.apply_move(move_);
+        }
+        for axis in AXES {
+            if solved.tip_solved(axis) {
+                continue;
+            }
+            let mut tip = PyraminxMove {
+                axis,
+                tip: true,
+                prime: false,
+            };
+            solved.apply_move(&tip);
+            if !solved.tip_solved(axis) {
+                tip.prime = true;
+                solved.apply_move(&tip);
+                solved.apply_move(&tip);
+            }
+            solution.push(tip);
+        }
+        solution
+    }
+
+    fn tip_solved(&self, axis: PyraminxAxis) -> bool {
+        let corner = axis.index();
+        face_corners(corner)
+            .iter()
+            .all(|&face| self.stickers[sticker(face, Piece::Tip, corner, 0)] == face as u8)
+    }
+
+    fn index(&self) -> usize {
+        self.edge_coordinate() * CENTRE_STATES + self.centre_coordinate(Piece::Centre)
+    }
+
+    fn from_index(index: usize) -> PyraminxState {
+        let mut state = PyraminxState::solved();
+        state.set_edge_coordinate(index / CENTRE_STATES);
+        state.set_centre_coordinate(Piece::Centre, index % CENTRE_STATES);
+        state
+    }
+
+    /// Edge permutation (as a rank out of 720) and flips (six bits). An edge
+    /// is flipped when the colour on the first face around its position is
+    /// the higher of its two colours.
+    fn edge_coordinate(&self) -> usize {
+        let mut permutation = [0usize; 6];
+        let mut flips = 0;
+        for (position, &(a, b)) in EDGES.iter().enumerate() {
+            let [c, d] = other_corners(a, b);
+            let first = self.stickers[sticker(c, Piece::Edge, a, b)] as usize;
+            let second = self.stickers[sticker(d, Piece::Edge, a, b)] as usize;
+            // An edge shows the colours of the two faces it is not opposite
+            // to, so its home is the pair of corners it shows no colour of.
+            let home = other_corners(first.min(second), first.max(second));
+            permutation[position] = EDGES
+                .iter()
+                .position(|&(x, y)| [x, y] == home)
+                .expect("edge colours name an edge");
+            flips = flips * 2 + (first > second) as usize;
+        }
+
+        let mut rank = 0;
+        for i in 0..6 {
+            let smaller = permutation[i + 1..]
+                .iter()
+                .filter(|&&p| p < permutation[i])
+                .count();
+            rank = rank * (6 - i) + smaller;
+        }
+        rank * 64 + flips
+    }
+
+    fn set_edge_coordinate(&mut self, coordinate: usize) {
+        let (mut rank, mut flips) = (coordinate / 64, coordinate % 64);
+        let mut digits = [0; 6];
+        for i in (0..6).rev() {
+            digits[i] = rank % (6 - i);
+            rank /= 6 - i;
+        }
+        let mut remaining: Vec<usize> = (0..6).collect();
+
+        let mut flipped = [false; 6];
+        for position in (0..6).rev() {
+            flipped[position] = flips % 2 == 1;
+            flips /= 2;
+        }
+
+        for (position, &(a, b)) in EDGES.iter().enumerate() {
+            let (x, y) = EDGES[remaining.remove(digits[position])];
+            let colours = other_corners(x, y);
+            let [c, d] = other_corners(a, b);
+            let (first, second) = if flipped[position] {
+                (colours[1], colours[0])
+            } else {
+                (colours[0], colours[1])
+            };
+            self.stickers[sticker(c, Piece::Edge, a, b)] = first as u8;
+            self.stickers[sticker(d, Piece::Edge, a, b)] = second as u8;
+        }
+    }
+
+    /// Twist of the four centres (or tips) in base 3. A piece's twist is how
+    /// far the colour of the first face around it has turned.
+    fn centre_coordinate(&self, piece: Piece) -> usize {
+        (0..4).fold(0, |acc, corner| {
+            let faces = face_corners(corner);
+            let colour = self.stickers[sticker(faces[0], piece, corner, 0)] as usize;
+            let twist = faces
+                .iter()
+                .position(|&f| f == colour)
+                .expect("colour is a face");
+            acc * 3 + twist
+        })
+    }
+
+    fn set_centre_coordinate(&mut self, piece: Piece, mut coordinate: usize) {
+        for corner in (0..4).rev() {
+            let twist = coordinate % 3;
+            coordinate /= 3;
+            let faces = face_corners(corner);
+            for (i, &face) in faces.iter().enumerate() {
+                self.stickers[sticker(face, piece, corner, 0)] = faces[(i + twist) % 3] as u8;
+            }
+        }
+    }
+}
+
+/// The two corners other than `a` and `b`, in increasing order.
+fn other_corners(a: usize, b: usize) -> [usize; 2] {
+    let mut others = (0..4).filter(|&c| c != a && c != b);
+    [others.next().unwrap(), others.next().unwrap()]
+}
+
+struct Tables {
+    /// Sticker permutations for a clockwise turn and tip turn of each corner,
+    /// where sticker `i` receives the sticker at `turn[i]`.
+    turns: [[usize; STICKERS]; 8],
+    edge_moves: Vec<[u16; 8]>,
+    centre_moves: Vec<[u8; 8]>,
+    distance: Vec<u8>,
+    reachable: Vec<u32>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+fn build_turn(corner: usize, tip_only: bool) -> [usize; STICKERS] {
+    let cycle = CORNER_CYCLES[corner];
+    let mut turn: [usize; STICKERS] = std::array::from_fn(|i| i);
+    for face in 0..4 {
+        if face == corner {
+            continue;
+        }
+        let corners = face_corners(face);
+        let mut pieces = vec![(Piece::Tip, corner, 0)];
+        if !tip_only {
+            pieces.push((Piece::Centre, corner, 0));
+            for &other in corners.iter().filter(|&&c| c != corner) {
+                pieces.push((Piece::Edge, corner.min(other), corner.max(other)));
+            }
+        }
+        for (piece, a, b) in pieces {
+            let to = match piece {
+                Piece::Edge => {
+                    let (x, y) = (cycle[a], cycle[b]);
+                    sticker(cycle[face], piece, x.min(y), x.max(y))
+                }
+                _ => sticker(cycle[face], piece, cycle[a], 0),
+            };
+            turn[to] = sticker(face, piece, a, b);
+        }
+    }
+    turn
+}
+
+fn permute(stickers: &mut [u8; STICKERS], turn: &[usize; STICKERS], times: usize) {
+    for _ in 0..times {
+        let previous = *stickers;
+        for (to, &from) in turn.iter().enumerate() {
+            stickers[to] = previous[from];
+        }
+    }
+}
+
+fn build_tables() -> Tables {
+    let turns: [[usize; STICKERS]; 8] = std::array::from_fn(|m| build_turn(m / 2, m % 2 == 1));
+
+    // Move tables index moves as `corner * 2 + prime`.
+    let turned = |mut state: PyraminxState, m: usize| {
+        permute(&mut state.stickers, &turns[m / 2 * 2], m % 2 + 1);
+        state
+    };
+    let edge_moves: Vec<[u16; 8]> = (0..EDGE_STATES)
+        .map(|coordinate| {
+            let mut state = PyraminxState::solved();
+            state.set_edge_coordinate(coordinate);
+            std::array::from_fn(|m| turned(state, m).edge_coordinate() as u16)
+        })
+        .collect();
+    let centre_moves: Vec<[u8; 8]> = (0..CENTRE_STATES)
+        .map(|coordinate| {
+            let mut state = PyraminxState::solved();
+            state.set_centre_coordinate(Piece::Centre, coordinate);
+            std::array::from_fn(|m| turned(state, m).centre_coordinate(Piece::Centre) as u8)
+        })
+        .collect();
+
+    let mut distance = vec![u8::MAX; EDGE_STATES * CENTRE_STATES];
+    let solved = PyraminxState::solved().index();
+    distance[solved] = 0;
+    let mut reachable = vec![solved as u32];
+    let mut start = 0;
+    let mut depth = 0;
+    while start < reachable.len() {
+        depth += 1;
+        let end = reachable.len();
+        for i in start..end {
+            let index = reachable[i] as usize;
+            let (edges, centres) = (index / CENTRE_STATES, index % CENTRE_STATES);
+            for m in 0..8 {
+                let neighbour = edge_moves[edges][m] as usize * CENTRE_STATES
+                    + centre_moves[centres][m] as usize;
+                if distance[neighbour] == u8::MAX {
+                    distance[neighbour] = depth;
+                    reachable.push(neighbour as u32);
+                }
+            }
+        }
+        start = end;
+    }
+
+    Tables {
+        turns,
+        edge_moves,
+        centre_moves,
+        distance,
+        reachable,
+    }
+}
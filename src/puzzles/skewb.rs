@@ -0,0 +1,403 @@
+use crate::scramble::seeded_rng;
+use rand::{Rng, rng};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// The four corners turned in WCA notation. The up-front-right corner is
+/// never turned, which keeps the puzzle's orientation fixed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SkewbAxis {
+    /// The down-right-back corner.
+    Right,
+    /// The up-left-back corner.
+    Up,
+    /// The down-left-front corner.
+    Left,
+    /// The down-left-back corner.
+    Back,
+}
+
+const AXES: [SkewbAxis; 4] = [
+    SkewbAxis::Right,
+    SkewbAxis::Up,
+    SkewbAxis::Left,
+    SkewbAxis::Back,
+];
+
+impl SkewbAxis {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Position of the corner, with x pointing right, y up and z front.
+    fn corner(&self) -> [i32; 3] {
+        match self {
+            SkewbAxis::Right => [1, -1, -1],
+            SkewbAxis::Up => [-1, 1, -1],
+            SkewbAxis::Left => [-1, -1, 1],
+            SkewbAxis::Back => [-1, -1, -1],
+        }
+    }
+}
+
+impl fmt::Display for SkewbAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkewbAxis::Right => write!(f, "R"),
+            SkewbAxis::Up => write!(f, "U"),
+            SkewbAxis::Left => write!(f, "L"),
+            SkewbAxis::Back => write!(f, "B"),
+        }
+    }
+}
+
+/// A 120 degree turn of half the puzzle around a corner, clockwise when
+/// looking at that corner.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SkewbMove {
+    pub axis: SkewbAxis,
+    pub prime: bool,
+}
+
+impl fmt::Display for SkewbMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.axis)?;
+        if self.prime {
+            write!(f, "'")?;
+        }
+        Ok(())
+    }
+}
+
+impl SkewbMove {
+    pub fn inverse(&self) -> SkewbMove {
+        SkewbMove {
+            prime: !self.prime,
+            ..*self
+        }
+    }
+}
+
+/// Random-state scrambles need at least this many moves to solve.
+pub const DEFAULT_MIN_DEPTH: usize = 7;
+/// Every state can be solved in at most this many moves.
+pub const MAX_DEPTH: usize = 11;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SkewbScramble {
+    pub moves: Vec<SkewbMove>,
+}
+
+impl fmt::Display for SkewbScramble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for move_ in &self.moves {
+            write!(f, "{move_} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl SkewbScramble {
+    pub fn generate() -> SkewbScramble {
+        SkewbScramble::generate_with_rng(&mut rng())
+    }
+
+    pub fn generate_seeded(seed: u64) -> SkewbScramble {
+        SkewbScramble::generate_with_rng(&mut seeded_rng(seed))
+    }
+
+    /// Picks a state uniformly from the 3,149,280 states at least
+    /// DEFAULT_MIN_DEPTH moves from solved, solves it optimally and inverts
+    /// the solution.
+    pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R) -> SkewbScramble {
+        let tables = tables();
+        let state = loop {
+            let index = rng.random_range(0..tables.distance.len());
+            if tables.distance[index] as usize >= DEFAULT_MIN_DEPTH {
+                break SkewbState::from_index(index);
+            }
+        };
+
+        let moves = state.solve().iter().rev().map(SkewbMove::inverse).collect();
+        SkewbScramble { moves }
+    }
+}
+
+const STICKERS: usize = 30;
+
+/// Outward normal of each face in the order U, R, F, D, L, B, followed by the
+/// directions of the face's rows and columns when drawn on a net.
+const FACES: [[[i32; 3]; 3]; 6] = [
+    [[0, 1, 0], [0, 0, 1], [1, 0, 0]],
+    [[1, 0, 0], [0, -1, 0], [0, 0, -1]],
+    [[0, 0, 1], [0, -1, 0], [1, 0, 0]],
+    [[0, -1, 0], [0, 0, -1], [1, 0, 0]],
+    [[-1, 0, 0], [0, -1, 0], [0, 0, 1]],
+    [[0, 0, -1], [0, -1, 0], [-1, 0, 0]],
+];
+
+/// Position and normal of every sticker. Each face holds its centre followed
+/// by its four corners in reading order.
+fn sticker_geometry() -> [([i32; 3], [i32; 3]); STICKERS] {
+    std::array::from_fn(|i| {
+        let [normal, down, right] = FACES[i / 5];
+        let position = match i % 5 {
+            0 => normal,
+            corner => {
+                let row = if corner < 3 { -1 } else { 1 };
+                let col = if corner % 2 == 1 { -1 } else { 1 };
+                std::array::from_fn(|a| normal[a] + row * down[a] + col * right[a])
+            }
+        };
+        (position, normal)
+    })
+}
+
+/// Sticker-level state of a Skewb. Each sticker holds the face it belongs to
+/// on a solved puzzle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SkewbState {
+    stickers: [u8; STICKERS],
+}
+
+impl Default for SkewbState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl SkewbState {
+    pub fn solved() -> SkewbState {
+        SkewbState {
+            stickers: std::array::from_fn(|i| (i / 5) as u8),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == SkewbState::solved()
+    }
+
+    /// Colour of a sticker: the face it was on when solved. Faces are in the
+    /// order U, R, F, D, L, B, each with its centre followed by its corners
+    /// in reading order.
+    pub fn stickers(&self) -> &[u8; STICKERS] {
+        &self.stickers
+    }
+
+    pub fn apply_move(&mut self, move_: &SkewbMove) {
+        let turn = &tables().turns[move_.axis.index()];
+        permute(&mut self.stickers, turn, if move_.prime { 2 } else { 1 });
+    }
+
+    pub fn apply_scramble(&mut self, scramble: &SkewbScramble) {
+        for move_ in &scramble.moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Number of moves in an optimal solution.
+    pub fn distance(&self) -> usize {
+        tables().distance[self.index()] as usize
+    }
+
+    /// An optimal solution.
+    pub fn solve(&self) -> Vec<SkewbMove> {
+        let tables = tables();
+        let mut solution = Vec::new();
+        let mut corners = tables.corners.coordinate(self) as usize;
+        let mut centres = tables.centres.coordinate(self) as usize;
+
+        loop {
+            let distance = tables.distance[corners * CENTRE_STATES + centres];
+            if distance == 0 {
+                break;
+            }
+            let (m, next_corners, next_centres) = (0..8)
+                .map(|m| {
+                    (
+                        m,
+                        tables.corners.moves[corners][m] as usize,
+                        tables.centres.moves[centres][m] as usize,
+                    )
+                })
+                .find(|&(_, c, e)| tables.distance[c * CENTRE_STATES + e] < distance)
+                .expect("every unsolved state has a move towards solved");
+            solution.push(SkewbMove {
+                axis: AXES[m / 2],
+                prime: m % 2 == 1,
+            });
+            corners = next_corners;
+            centres = next_centres;
+        }
+        solution
+    }
+
+    fn index(&self) -> usize {
+        let tables = tables();
+        tables.corners.coordinate(self) as usize * CENTRE_STATES
+            + tables.centres.coordinate(self) as usize
+    }
+
+    fn from_index(index: usize) -> SkewbState {
+        let tables = tables();
+        let corners = &tables.corners.states[index / CENTRE_STATES];
+        let centres = &tables.centres.states[index % CENTRE_STATES];
+        SkewbState {
+            stickers: std::array::from_fn(|i| {
+                if i % 5 == 0 {
+                    centres.stickers[i]
+                } else {
+                    corners.stickers[i]
+                }
+            }),
+        }
+    }
+}
+
+const CORNER_STATES: usize = 8748;
+const CENTRE_STATES: usize = 360;
+
+/// The reachable arrangements of one kind of piece, numbered in the order a
+/// breadth-first search from solved finds them.
+struct Coordinate<const N: usize> {
+    key: fn(&SkewbState) -> [u8; N],
+    numbers: HashMap<[u8; N], u16>,
+    /// A state with each arrangement, used to rebuild states from numbers.
+    states: Vec<SkewbState>,
+    /// Move tables index moves as `axis * 2 + prime`.
+    moves: Vec<[u16; 8]>,
+}
+
+impl<const N: usize> Coordinate<N> {
+    fn explore(key: fn(&SkewbState) -> [u8; N], turns: &[[usize; STICKERS]; 4]) -> Self {
+        let solved = SkewbState::solved();
+        let mut numbers = HashMap::from([(key(&solved), 0)]);
+        let mut states = vec![solved];
+        let mut moves = Vec::new();
+        while moves.len() < states.len() {
+            let state = states[moves.len()];
+            let row = std::array::from_fn(|m| {
+                let mut next = state;
+                permute(&mut next.stickers, &turns[m / 2], m % 2 + 1);
+                let number = numbers.len() as u16;
+                *numbers.entry(key(&next)).or_insert_with(|| {
+                    states.push(next);
+                    number
+                })
+            });
+            moves.push(row);
+        }
+        Coordinate {
+            key,
+            numbers,
+            states,
+            moves,
+        }
+    }
+
+    fn coordinate(&self, state: &SkewbState) -> u16 {
+        self.numbers[&(self.key)(state)]
+    }
+}
+
+fn corner_key(state: &SkewbState) -> [u8; 24] {
+    std::array::from_fn(|i| state.stickers[i / 4 * 5 + i % 4 + 1])
+}
+
+fn centre_key(state: &SkewbState) -> [u8; 6] {
+    std::array::from_fn(|i| state.stickers[i * 5])
+}
+
+struct Tables {
+    /// Sticker permutations for a clockwise turn of each corner, where
+    /// sticker `i` receives the sticker at `turn[i]`.
+    turns: [[usize; STICKERS]; 4],
+    corners: Coordinate<24>,
+    centres: Coordinate<6>,
+    distance: Vec<u8>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+/// Turns the half of the puzzle nearer `corner` clockwise around it. A
+/// clockwise turn around the corner at (1, 1, 1) takes (x, y, z) to (y, z, x);
+/// mirroring it into another corner reverses its direction when an odd number
+/// of axes are flipped.
+fn build_turn(corner: [i32; 3]) -> [usize; STICKERS] {
+    let mirrored = corner.iter().product::<i32>() < 0;
+    let rotate = |v: [i32; 3]| {
+        let v = [v[0] * corner[0], v[1] * corner[1], v[2] * corner[2]];
+        let v = if mirrored {
+            [v[2], v[0], v[1]]
+        } else {
+            [v[1], v[2], v[0]]
+        };
+        [v[0] * corner[0], v[1] * corner[1], v[2] * corner[2]]
+    };
+    let dot = |v: [i32; 3]| v[0] * corner[0] + v[1] * corner[1] + v[2] * corner[2];
+
+    let geometry = sticker_geometry();
+    let mut turn: [usize; STICKERS] = std::array::from_fn(|i| i);
+    for (from, &(position, normal)) in geometry.iter().enumerate() {
+        if dot(position) > 0 {
+            let target = (rotate(position), rotate(normal));
+            let to = geometry
+                .iter()
+                .position(|&sticker| sticker == target)
+                .expect("turns map stickers to stickers");
+            turn[to] = from;
+        }
+    }
+    turn
+}
+
+fn permute(stickers: &mut [u8; STICKERS], turn: &[usize; STICKERS], times: usize) {
+    for _ in 0..times {
+        let previous = *stickers;
+        for (to, &from) in turn.iter().enumerate() {
+            stickers[to] = previous[from];
+        }
+    }
+}
+
+fn build_tables() -> Tables {
+    let turns = AXES.map(|axis| build_turn(axis.corner()));
+    let corners = Coordinate::explore(corner_key, &turns);
+    let centres = Coordinate::explore(centre_key, &turns);
+    debug_assert_eq!(corners.states.len(), CORNER_STATES);
+    debug_assert_eq!(centres.states.len(), CENTRE_STATES);
+
+    let mut distance = vec![u8::MAX; CORNER_STATES * CENTRE_STATES];
+    distance[0] = 0;
+    let mut frontier = vec![0usize];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for index in frontier {
+            let (c, e) = (index / CENTRE_STATES, index % CENTRE_STATES);
+            for m in 0..8 {
+                let neighbour =
+                    corners.moves[c][m] as usize * CENTRE_STATES + centres.moves[e][m] as usize;
+                if distance[neighbour] == u8::MAX {
+                    distance[neighbour] = depth;
+                    next.push(neighbour);
+                }
+            }
+        }
+        frontier = next;
+    }
+    debug_assert_eq!(depth - 1, MAX_DEPTH as u8);
+    debug_assert!(distance.iter().all(|&d| d != u8::MAX));
+
+    Tables {
+        turns,
+        corners,
+        centres,
+        distance,
+    }
+}
@@ -0,0 +1,922 @@
+use crate::scramble::seeded_rng;
+use rand::seq::SliceRandom;
+use rand::{Rng, rng};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// A move in WCA notation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Square1Move {
+    /// `(x,y)`: turns the top layer `x` and the bottom layer `y` twelfths of
+    /// a turn clockwise, each as seen from its own face.
+    Turn { top: i8, bottom: i8 },
+    /// `/`: turns the right half of the puzzle 180 degrees.
+    Slash,
+}
+
+impl fmt::Display for Square1Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Square1Move::Turn { top, bottom } => write!(f, "({top},{bottom})"),
+            Square1Move::Slash => write!(f, "/"),
+        }
+    }
+}
+
+impl Square1Move {
+    pub fn inverse(&self) -> Square1Move {
+        match *self {
+            Square1Move::Turn { top, bottom } => Square1Move::Turn {
+                top: normalized_turn(-top as i32),
+                bottom: normalized_turn(-bottom as i32),
+            },
+            Square1Move::Slash => Square1Move::Slash,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Square1Scramble {
+    pub moves: Vec<Square1Move>,
+}
+
+impl fmt::Display for Square1Scramble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for move_ in &self.moves {
+            write!(f, "{move_} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl Square1Scramble {
+    pub fn generate() -> Square1Scramble {
+        Square1Scramble::generate_with_rng(&mut rng())
+    }
+
+    pub fn generate_seeded(seed: u64) -> Square1Scramble {
+        Square1Scramble::generate_with_rng(&mut seeded_rng(seed))
+    }
+
+    /// Picks a state uniformly from every state the puzzle can reach where a
+    /// slash is possible, and returns the inverse of a two-phase solution.
+    /// Following the solution backwards passes through the same states, so
+    /// every slash in the scramble is legal.
+    pub fn generate_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Square1Scramble {
+        let state = Square1State::random(rng);
+        let moves = state
+            .solve()
+            .iter()
+            .rev()
+            .map(Square1Move::inverse)
+            .collect();
+        Square1Scramble { moves }
+    }
+}
+
+const UNITS: usize = 12;
+const CORNERS: u8 = 8;
+
+/// Where each layer's corners start in the solved state, and the layer type
+/// the solver's second phase uses for the top layer. Bit `i` is set when a
+/// corner covers units `i` and `i + 1`.
+const CORNER_FIRST: u16 = 0b0010_0100_1001;
+const EDGE_FIRST: u16 = CORNER_FIRST << 1;
+
+/// State of a Square-1. Each layer is listed a twelfth of a turn at a time,
+/// clockwise as seen from above, starting at the front end of the slice.
+/// Corners are numbered 0 to 7 and take up two entries, edges are numbered 8
+/// to 15, and the top layer's pieces come first in each group.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Square1State {
+    top: [u8; UNITS],
+    bottom: [u8; UNITS],
+    middle_flipped: bool,
+}
+
+impl Default for Square1State {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl Square1State {
+    pub fn solved() -> Square1State {
+        Square1State {
+            top: [0, 0, 8, 1, 1, 9, 2, 2, 10, 3, 3, 11],
+            bottom: [4, 4, 12, 5, 5, 13, 6, 6, 14, 7, 7, 15],
+            middle_flipped: false,
+        }
+    }
+
+    /// A state picked uniformly from those where a slash is possible.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Square1State {
+        let shapes = &tables().phase1.shapes;
+        let (top, bottom) = shapes[rng.random_range(0..shapes.len())];
+        let mut corners: Vec<u8> = (0..CORNERS).collect();
+        let mut edges: Vec<u8> = (CORNERS..2 * CORNERS).collect();
+        corners.shuffle(rng);
+        edges.shuffle(rng);
+        let mut state = Square1State::from_shape(top, bottom, &corners, &edges);
+        state.middle_flipped = rng.random_bool(0.5);
+        state
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == Square1State::solved()
+    }
+
+    pub fn top(&self) -> &[u8; UNITS] {
+        &self.top
+    }
+
+    pub fn bottom(&self) -> &[u8; UNITS] {
+        &self.bottom
+    }
+
+    pub fn is_middle_flipped(&self) -> bool {
+        self.middle_flipped
+    }
+
+    /// Whether a slash is possible, i.e. no corner crosses the slice.
+    pub fn is_twistable(&self) -> bool {
+        [self.top, self.bottom]
+            .iter()
+            .all(|layer| layer[11] != layer[0] && layer[5] != layer[6])
+    }
+
+    /// # Panics
+    ///
+    /// Panics on a slash when a corner blocks the slice.
+    pub fn apply_move(&mut self, move_: &Square1Move) {
+        match *move_ {
+            Square1Move::Turn { top, bottom } => self.turn(top as i32, bottom as i32),
+            Square1Move::Slash => {
+                assert!(self.is_twistable(), "a corner blocks the slash");
+                self.slash();
+            }
+        }
+    }
+
+    pub fn apply_scramble(&mut self, scramble: &Square1Scramble) {
+        for move_ in &scramble.moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Solves the shape first, then the pieces without leaving cube shape.
+    /// Solutions are typically 12 to 14 slashes long.
+    pub fn solve(&self) -> Vec<Square1Move> {
+        let tables = tables();
+        let mut state = *self;
+        let mut moves = Vec::new();
+
+        // Every reachable state is a turn away from one where a slash is
+        // possible.
+        if !state.is_twistable() {
+            let (top, bottom) = (0..UNITS as i32 * UNITS as i32)
+                .map(|turn| (turn / 12, turn % 12))
+                .find(|&(top, bottom)| {
+                    let mut turned = state;
+                    turned.turn(top, bottom);
+                    turned.is_twistable()
+                })
+                .expect("reachable states have a twistable alignment");
+            state.push_turn(&mut moves, top, bottom);
+        }
+
+        let phase1 = &tables.phase1;
+        let mut node = phase1.node(&state);
+        while phase1.distance[node] > 0 {
+            let step = phase1.steps[node / 2]
+                .iter()
+                .find(|step| phase1.distance[Phase1::next(node, step)] < phase1.distance[node])
+                .expect("every unsolved shape has a step towards cube shape");
+            state.push_turn(&mut moves, step.top as i32, step.bottom as i32);
+            state.slash();
+            moves.push(Square1Move::Slash);
+            node = Phase1::next(node, step);
+        }
+
+        // Line the layers up so a slash keeps cube shape.
+        let top = if state.top_shape() == CORNER_FIRST {
+            1
+        } else {
+            0
+        };
+        let bottom = if state.bottom_shape() == EDGE_FIRST {
+            1
+        } else {
+            0
+        };
+        state.push_turn(&mut moves, top, bottom);
+
+        for step in tables.phase2.solve(&state) {
+            let (before, after) = step_turns(step);
+            state.push_turn(&mut moves, before.0, before.1);
+            state.slash();
+            moves.push(Square1Move::Slash);
+            state.push_turn(&mut moves, after.0, after.1);
+        }
+        let (top, bottom) = (0..TURNS)
+            .map(quarter_turns)
+            .find(|&(top, bottom)| {
+                let mut turned = state;
+                turned.turn(top - 1, bottom);
+                turned.is_solved()
+            })
+            .expect("second phase ends a turn away from solved");
+        state.push_turn(&mut moves, top - 1, bottom);
+        debug_assert!(state.is_solved());
+
+        simplify(moves)
+    }
+
+    fn turn(&mut self, top: i32, bottom: i32) {
+        let (old_top, old_bottom) = (self.top, self.bottom);
+        for i in 0..UNITS {
+            self.top[(i as i32 + top).rem_euclid(12) as usize] = old_top[i];
+            self.bottom[(i as i32 - bottom).rem_euclid(12) as usize] = old_bottom[i];
+        }
+    }
+
+    fn push_turn(&mut self, moves: &mut Vec<Square1Move>, top: i32, bottom: i32) {
+        self.turn(top, bottom);
+        moves.push(Square1Move::Turn {
+            top: normalized_turn(top),
+            bottom: normalized_turn(bottom),
+        });
+    }
+
+    /// Swaps the right halves of the layers. The half turn sends the unit
+    /// `i` twelfths clockwise from the front of one layer to `17 - i` on the
+    /// other.
+    fn slash(&mut self) {
+        for i in 6..UNITS {
+            std::mem::swap(&mut self.top[i], &mut self.bottom[17 - i]);
+        }
+        self.middle_flipped = !self.middle_flipped;
+    }
+
+    fn top_shape(&self) -> u16 {
+        layer_shape(&self.top)
+    }
+
+    fn bottom_shape(&self) -> u16 {
+        layer_shape(&self.bottom)
+    }
+
+    /// Parity of the pieces read clockwise from the front of each layer, top
+    /// layer first. In cube shape with the layers lined up for the second
+    /// phase, only states with the parity of solved can be solved without
+    /// leaving cube shape.
+    fn parity(&self) -> bool {
+        let mut pieces = Vec::with_capacity(16);
+        for layer in [&self.top, &self.bottom] {
+            for i in 0..UNITS {
+                if i == 0 || layer[i] != layer[i - 1] {
+                    pieces.push(layer[i]);
+                }
+            }
+        }
+        permutation_parity(&pieces)
+    }
+
+    /// A state with the given layer shapes, placing corners and edges in the
+    /// given order.
+    fn from_shape(top: u16, bottom: u16, corners: &[u8], edges: &[u8]) -> Square1State {
+        let mut corners = corners.iter();
+        let mut edges = edges.iter();
+        let mut fill = |shape: u16| {
+            let mut layer = [0; UNITS];
+            let mut i = 0;
+            while i < UNITS {
+                if shape & 1 << i != 0 {
+                    let corner = *corners.next().expect("shapes hold eight corners");
+                    layer[i] = corner;
+                    layer[i + 1] = corner;
+                    i += 2;
+                } else {
+                    layer[i] = *edges.next().expect("shapes hold eight edges");
+                    i += 1;
+                }
+            }
+            layer
+        };
+        let top = fill(top);
+        let bottom = fill(bottom);
+        Square1State {
+            top,
+            bottom,
+            middle_flipped: false,
+        }
+    }
+}
+
+/// Bit `i` is set when a corner starts at unit `i`. Only meaningful for
+/// layers where no corner crosses the front of the slice.
+fn layer_shape(layer: &[u8; UNITS]) -> u16 {
+    (0..UNITS - 1)
+        .filter(|&i| layer[i] < CORNERS && layer[i + 1] == layer[i])
+        .fold(0, |shape, i| shape | 1 << i)
+}
+
+/// A turn in twelfths as written in scrambles, from -5 to 6.
+fn normalized_turn(turn: i32) -> i8 {
+    match turn.rem_euclid(12) {
+        turn @ 0..=6 => turn as i8,
+        turn => turn as i8 - 12,
+    }
+}
+
+/// Merges neighbouring turns, then drops empty turns and the pairs of
+/// slashes that leaves next to each other.
+fn simplify(moves: Vec<Square1Move>) -> Vec<Square1Move> {
+    let mut simplified: Vec<Square1Move> = Vec::with_capacity(moves.len());
+    for move_ in moves {
+        match (simplified.last(), move_) {
+            (Some(Square1Move::Slash), Square1Move::Slash) => {
+                simplified.pop();
+            }
+            (Some(&Square1Move::Turn { top, bottom }), Square1Move::Turn { top: t, bottom: b }) => {
+                simplified.pop();
+                let top = normalized_turn(top as i32 + t as i32);
+                let bottom = normalized_turn(bottom as i32 + b as i32);
+                if top != 0 || bottom != 0 {
+                    simplified.push(Square1Move::Turn { top, bottom });
+                }
+            }
+            (_, Square1Move::Turn { top: 0, bottom: 0 }) => {}
+            (_, move_) => simplified.push(move_),
+        }
+    }
+    simplified
+}
+
+fn permutation_parity(permutation: &[u8]) -> bool {
+    let mut parity = false;
+    for i in 0..permutation.len() {
+        for j in i + 1..permutation.len() {
+            if permutation[i] > permutation[j] {
+                parity = !parity;
+            }
+        }
+    }
+    parity
+}
+
+/// The rank of a permutation of `0..8` in lexicographic order. Each piece
+/// counts the smaller pieces after it, found from those already seen.
+fn rank_permutation(permutation: &[u8; 8]) -> usize {
+    let mut seen = 0u32;
+    let mut rank = 0;
+    for (i, &p) in permutation.iter().enumerate() {
+        let smaller_before = (seen & ((1 << p) - 1)).count_ones() as usize;
+        rank = rank * (8 - i) + p as usize - smaller_before;
+        seen |= 1 << p;
+    }
+    rank
+}
+
+fn unrank_permutation(mut rank: usize, permutation: &mut [u8]) {
+    let n = permutation.len();
+    let mut digits = vec![0; n];
+    for i in (0..n).rev() {
+        digits[i] = rank % (n - i);
+        rank /= n - i;
+    }
+    let mut remaining: Vec<u8> = (0..n as u8).collect();
+    for i in 0..n {
+        permutation[i] = remaining.remove(digits[i]);
+    }
+}
+
+struct Tables {
+    phase1: Phase1,
+    phase2: Phase2,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| Tables {
+        phase1: Phase1::build(),
+        phase2: Phase2::build(),
+    })
+}
+
+/// A turn followed by a slash, from one twistable shape to another.
+struct Step {
+    top: u8,
+    bottom: u8,
+    next: usize,
+    /// Whether the step changes the parity of the pieces.
+    parity: bool,
+}
+
+/// The first phase brings the puzzle into cube shape with the parity the
+/// second phase can solve. Its nodes are twistable shapes paired with that
+/// parity, `shape * 2 + parity`.
+struct Phase1 {
+    shapes: Vec<(u16, u16)>,
+    numbers: HashMap<(u16, u16), usize>,
+    steps: Vec<Vec<Step>>,
+    distance: Vec<u8>,
+}
+
+impl Phase1 {
+    fn build() -> Phase1 {
+        // No corner may overlap another or cross the slice.
+        let layers: Vec<u16> = (0..1 << UNITS)
+            .filter(|&shape: &u16| {
+                shape & (shape << 1 | shape >> 11) == 0 && shape & (1 << 5 | 1 << 11) == 0
+            })
+            .collect();
+        let shapes: Vec<(u16, u16)> = layers
+            .iter()
+            .flat_map(|&top| layers.iter().map(move |&bottom| (top, bottom)))
+            .filter(|&(top, bottom)| top.count_ones() + bottom.count_ones() == CORNERS as u32)
+            .collect();
+        let numbers: HashMap<(u16, u16), usize> = shapes
+            .iter()
+            .enumerate()
+            .map(|(i, &shape)| (shape, i))
+            .collect();
+
+        let pieces: Vec<u8> = (0..2 * CORNERS).collect();
+        let steps: Vec<Vec<Step>> = shapes
+            .iter()
+            .map(|&(top, bottom)| {
+                let state = Square1State::from_shape(top, bottom, &pieces[..8], &pieces[8..]);
+                let mut steps = Vec::new();
+                for turn_top in 0..UNITS as u8 {
+                    for turn_bottom in 0..UNITS as u8 {
+                        let mut next = state;
+                        next.turn(turn_top as i32, turn_bottom as i32);
+                        if !next.is_twistable() {
+                            continue;
+                        }
+                        next.slash();
+                        steps.push(Step {
+                            top: turn_top,
+                            bottom: turn_bottom,
+                            next: numbers[&(next.top_shape(), next.bottom_shape())],
+                            parity: next.parity() != state.parity(),
+                        });
+                    }
+                }
+                steps
+            })
+            .collect();
+
+        // Cube shapes are solved once lining them up for the second phase
+        // gives the parity of its goal.
+        let goal_parity = Phase2::goal().parity();
+        let mut distance = vec![u8::MAX; shapes.len() * 2];
+        for (number, &(top, bottom)) in shapes.iter().enumerate() {
+            if ![top, bottom]
+                .iter()
+                .all(|&s| s == CORNER_FIRST || s == EDGE_FIRST)
+            {
+                continue;
+            }
+            let mut state = Square1State::from_shape(top, bottom, &pieces[..8], &pieces[8..]);
+            let parity = state.parity();
+            state.turn((top == CORNER_FIRST) as i32, (bottom == EDGE_FIRST) as i32);
+            let changed = state.parity() != parity;
+            distance[number * 2 + (goal_parity != changed) as usize] = 0;
+        }
+
+        let mut depth = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            depth += 1;
+            for node in 0..distance.len() {
+                if distance[node] != u8::MAX {
+                    continue;
+                }
+                let reaches = steps[node / 2]
+                    .iter()
+                    .any(|step| distance[Phase1::next(node, step)] == depth - 1);
+                if reaches {
+                    distance[node] = depth;
+                    changed = true;
+                }
+            }
+        }
+        debug_assert!(distance.iter().all(|&d| d != u8::MAX));
+
+        Phase1 {
+            shapes,
+            numbers,
+            steps,
+            distance,
+        }
+    }
+
+    fn next(node: usize, step: &Step) -> usize {
+        step.next * 2 + ((node % 2 == 1) != step.parity) as usize
+    }
+
+    fn node(&self, state: &Square1State) -> usize {
+        self.numbers[&(state.top_shape(), state.bottom_shape())] * 2 + state.parity() as usize
+    }
+}
+
+const PERMUTATIONS: usize = 40320;
+const SEPARATIONS: usize = 70;
+/// Quarter turns of both layers, as `top * 4 + bottom`.
+const TURNS: usize = 16;
+/// Steps of a turn and a slash that keep cube shape. The first `TURNS` keep
+/// the layers lined up as they are. The rest turn each layer two twelfths
+/// further, which puts the corners beside different edges, and line the
+/// layers up again after the slash.
+const STEPS: usize = 2 * TURNS;
+
+fn quarter_turns(turn: usize) -> (i32, i32) {
+    (turn as i32 / 4 * 3, turn as i32 % 4 * 3)
+}
+
+/// The turns before and after the slash of a step.
+fn step_turns(step: usize) -> ((i32, i32), (i32, i32)) {
+    let (top, bottom) = quarter_turns(step % TURNS);
+    if step < TURNS {
+        ((top, bottom), (0, 0))
+    } else {
+        ((top + 2, bottom + 2), (1, 1))
+    }
+}
+
+/// Units holding corners and edges in the second phase, where the top layer
+/// starts with an edge and the bottom with a corner so that slashing keeps
+/// cube shape. Top layer slots come first.
+const CORNER_UNITS: [(bool, usize); 8] = [
+    (true, 1),
+    (true, 4),
+    (true, 7),
+    (true, 10),
+    (false, 0),
+    (false, 3),
+    (false, 6),
+    (false, 9),
+];
+const EDGE_UNITS: [(bool, usize); 8] = [
+    (true, 0),
+    (true, 3),
+    (true, 6),
+    (true, 9),
+    (false, 2),
+    (false, 5),
+    (false, 8),
+    (false, 11),
+];
+
+/// Where each slot of one kind takes its piece from after the given moves,
+/// found by following numbered pieces from the goal.
+fn slot_sources(
+    units: &[(bool, usize); 8],
+    first: u8,
+    apply: impl Fn(&mut Square1State),
+) -> [usize; 8] {
+    let mut state = Phase2::goal();
+    set_slots(&mut state, units, &[0, 1, 2, 3, 4, 5, 6, 7], first);
+    apply(&mut state);
+    slots(&state, units, first).map(usize::from)
+}
+
+/// Move tables for a coordinate of one kind of piece in cube shape, as
+/// `table[turn or step][coordinate]`.
+struct Moves {
+    turns: Vec<Vec<u16>>,
+    steps: Vec<Vec<u16>>,
+}
+
+impl Moves {
+    /// Builds the tables from the placement of pieces behind each
+    /// coordinate, moving them between slots as the turns and steps do.
+    fn build(
+        units: &[(bool, usize); 8],
+        first: u8,
+        placements: &[[u8; 8]],
+        get: impl Fn(&[u8; 8]) -> usize,
+    ) -> Moves {
+        let table = |sources: [usize; 8]| -> Vec<u16> {
+            placements
+                .iter()
+                .map(|placement| get(&sources.map(|source| placement[source])) as u16)
+                .collect()
+        };
+
+        let turns: Vec<Vec<u16>> = (0..TURNS)
+            .map(|turn| {
+                let (top, bottom) = quarter_turns(turn);
+                table(slot_sources(units, first, |state| state.turn(top, bottom)))
+            })
+            .collect();
+        // Every step is a quarter turn followed by one of these.
+        let slash = table(slot_sources(units, first, |state| state.slash()));
+        let switch = table(slot_sources(units, first, |state| {
+            state.turn(2, 2);
+            state.slash();
+            state.turn(1, 1);
+        }));
+        let steps = [&slash, &switch]
+            .iter()
+            .flat_map(|then| {
+                turns
+                    .iter()
+                    .map(|turn| turn.iter().map(|&c| then[c as usize]).collect())
+            })
+            .collect();
+        Moves { turns, steps }
+    }
+
+    fn permutations(units: &[(bool, usize); 8], first: u8) -> Moves {
+        let placements: Vec<[u8; 8]> = (0..PERMUTATIONS)
+            .map(|coordinate| {
+                let mut permutation = [0; 8];
+                unrank_permutation(coordinate, &mut permutation);
+                permutation
+            })
+            .collect();
+        Moves::build(units, first, &placements, rank_permutation)
+    }
+
+    fn layers(units: &[(bool, usize); 8], first: u8) -> Moves {
+        let (numbers, placements) = separations();
+        Moves::build(units, first, placements, |pieces| {
+            numbers[separation(pieces)]
+        })
+    }
+}
+
+/// Distances in steps from the goal for the pieces of one kind, together
+/// with which layer each piece of the other kind is on.
+///
+/// Quarter turns of the layers lead to the same steps, so they never change
+/// the distance. The table only keeps each permutation turned to the least
+/// coordinate it can reach, which makes it sixteen times smaller.
+struct Pruning {
+    /// The quarter turn taking each permutation to its least turn.
+    least_turn: Vec<u8>,
+    /// The number of the least turn of each permutation.
+    classes: Vec<u16>,
+    /// The least turns in order of their numbers.
+    least: Vec<u16>,
+    /// Indexed by `(number * SEPARATIONS + layers) * 2 + middle_flipped`.
+    distance: Vec<u8>,
+}
+
+impl Pruning {
+    fn build(pieces: &Moves, layers: &Moves, goal_pieces: usize, goal_layers: usize) -> Pruning {
+        let least_turn: Vec<u8> = (0..PERMUTATIONS)
+            .map(|p| {
+                (0..TURNS)
+                    .min_by_key(|&turn| pieces.turns[turn][p])
+                    .expect("there are quarter turns") as u8
+            })
+            .collect();
+        let least: Vec<u16> = (0..PERMUTATIONS)
+            .filter(|&p| least_turn[p] == 0)
+            .map(|p| p as u16)
+            .collect();
+        let mut numbers = vec![0; PERMUTATIONS];
+        for (number, &p) in least.iter().enumerate() {
+            numbers[p as usize] = number as u16;
+        }
+        let classes = (0..PERMUTATIONS)
+            .map(|p| numbers[pieces.turns[least_turn[p] as usize][p] as usize])
+            .collect();
+        let mut pruning = Pruning {
+            least_turn,
+            classes,
+            least,
+            distance: vec![u8::MAX; PERMUTATIONS / TURNS * SEPARATIONS * 2],
+        };
+
+        let start = pruning.index(layers, goal_pieces, goal_layers, false);
+        pruning.distance[start] = 0;
+        let mut frontier = vec![start];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next = Vec::new();
+            for node in frontier {
+                let (coordinates, flipped) = (node / 2, node % 2 == 1);
+                let p = pruning.least[coordinates / SEPARATIONS] as usize;
+                let l = coordinates % SEPARATIONS;
+                for step in 0..STEPS {
+                    let reached = pruning.index(
+                        layers,
+                        pieces.steps[step][p] as usize,
+                        layers.steps[step][l] as usize,
+                        !flipped,
+                    );
+                    if pruning.distance[reached] == u8::MAX {
+                        pruning.distance[reached] = depth;
+                        next.push(reached);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        pruning
+    }
+
+    fn index(&self, layers: &Moves, p: usize, l: usize, flipped: bool) -> usize {
+        let turn = self.least_turn[p] as usize;
+        let number = self.classes[p] as usize;
+        (number * SEPARATIONS + layers.turns[turn][l] as usize) * 2 + flipped as usize
+    }
+
+    fn distance(&self, layers: &Moves, p: usize, l: usize, flipped: bool) -> u8 {
+        self.distance[self.index(layers, p, l, flipped)]
+    }
+}
+
+/// Coordinates of a state in cube shape, lined up for the second phase.
+#[derive(Clone, Copy)]
+struct Coordinates {
+    corners: usize,
+    edges: usize,
+    corner_layers: usize,
+    edge_layers: usize,
+    middle_flipped: bool,
+}
+
+/// The second phase solves the pieces with steps that keep cube shape. It
+/// searches with IDA*, pruned by the corners with the edges' layers and the
+/// edges with the corners' layers.
+struct Phase2 {
+    corners: Moves,
+    edges: Moves,
+    corner_layers: Moves,
+    edge_layers: Moves,
+    corner_pruning: Pruning,
+    edge_pruning: Pruning,
+}
+
+impl Phase2 {
+    /// The solved state lined up for the second phase.
+    fn goal() -> Square1State {
+        let mut goal = Square1State::solved();
+        goal.turn(1, 0);
+        goal
+    }
+
+    fn build() -> Phase2 {
+        let corners = Moves::permutations(&CORNER_UNITS, 0);
+        let edges = Moves::permutations(&EDGE_UNITS, CORNERS);
+        let corner_layers = Moves::layers(&CORNER_UNITS, 0);
+        let edge_layers = Moves::layers(&EDGE_UNITS, CORNERS);
+        let goal = Phase2::coordinates(&Phase2::goal());
+        let corner_pruning = Pruning::build(&corners, &edge_layers, goal.corners, goal.edge_layers);
+        let edge_pruning = Pruning::build(&edges, &corner_layers, goal.edges, goal.corner_layers);
+        Phase2 {
+            corners,
+            edges,
+            corner_layers,
+            edge_layers,
+            corner_pruning,
+            edge_pruning,
+        }
+    }
+
+    fn coordinates(state: &Square1State) -> Coordinates {
+        let (numbers, _) = separations();
+        let corners = slots(state, &CORNER_UNITS, 0);
+        let edges = slots(state, &EDGE_UNITS, CORNERS);
+        Coordinates {
+            corners: rank_permutation(&corners),
+            edges: rank_permutation(&edges),
+            corner_layers: numbers[separation(&corners)],
+            edge_layers: numbers[separation(&edges)],
+            middle_flipped: state.middle_flipped,
+        }
+    }
+
+    fn step(&self, c: Coordinates, step: usize) -> Coordinates {
+        Coordinates {
+            corners: self.corners.steps[step][c.corners] as usize,
+            edges: self.edges.steps[step][c.edges] as usize,
+            corner_layers: self.corner_layers.steps[step][c.corner_layers] as usize,
+            edge_layers: self.edge_layers.steps[step][c.edge_layers] as usize,
+            middle_flipped: !c.middle_flipped,
+        }
+    }
+
+    fn lower_bound(&self, c: Coordinates) -> u8 {
+        let corners = self.corner_pruning.distance(
+            &self.edge_layers,
+            c.corners,
+            c.edge_layers,
+            c.middle_flipped,
+        );
+        let edges = self.edge_pruning.distance(
+            &self.corner_layers,
+            c.edges,
+            c.corner_layers,
+            c.middle_flipped,
+        );
+        corners.max(edges)
+    }
+
+    /// Whether the state is a quarter turn of each layer away from the goal.
+    fn is_solved(&self, c: Coordinates, goal: Coordinates) -> bool {
+        !c.middle_flipped
+            && (0..TURNS).any(|turn| {
+                self.corners.turns[turn][c.corners] as usize == goal.corners
+                    && self.edges.turns[turn][c.edges] as usize == goal.edges
+            })
+    }
+
+    /// An optimal sequence of steps to a quarter turn away from the goal.
+    fn solve(&self, state: &Square1State) -> Vec<usize> {
+        let start = Phase2::coordinates(state);
+        let goal = Phase2::coordinates(&Phase2::goal());
+        let mut path = Vec::new();
+        for depth in self.lower_bound(start).. {
+            if self.search(start, goal, depth, &mut path) {
+                return path;
+            }
+        }
+        unreachable!("the search deepens until it finds a solution")
+    }
+
+    fn search(&self, c: Coordinates, goal: Coordinates, depth: u8, path: &mut Vec<usize>) -> bool {
+        if self.is_solved(c, goal) {
+            return true;
+        }
+        if depth == 0 || self.lower_bound(c) > depth {
+            return false;
+        }
+        // A bare slash straight after another would undo it.
+        let first = if path.last().is_some_and(|&step| step < TURNS) {
+            1
+        } else {
+            0
+        };
+        for step in first..STEPS {
+            path.push(step);
+            if self.search(self.step(c, step), goal, depth - 1, path) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+/// The pieces of one kind in the given slots, numbered from `first`.
+fn slots(state: &Square1State, units: &[(bool, usize); 8], first: u8) -> [u8; 8] {
+    units.map(|(top, unit)| {
+        let layer = if top { &state.top } else { &state.bottom };
+        layer[unit] - first
+    })
+}
+
+fn set_slots(state: &mut Square1State, units: &[(bool, usize); 8], pieces: &[u8], first: u8) {
+    for (&(top, unit), &piece) in units.iter().zip(pieces) {
+        let layer = if top {
+            &mut state.top
+        } else {
+            &mut state.bottom
+        };
+        layer[unit] = piece + first;
+        if first == 0 {
+            layer[unit + 1] = piece;
+        }
+    }
+}
+
+/// Which slots hold pieces from the top layer, as a bitmask.
+fn separation(pieces: &[u8; 8]) -> usize {
+    (0..8)
+        .filter(|&i| pieces[i] < 4)
+        .fold(0, |mask, i| mask | 1 << i)
+}
+
+/// Numbers for the 70 ways to split pieces evenly between the layers, and
+/// a placement of pieces for each.
+fn separations() -> &'static (Vec<usize>, Vec<[u8; 8]>) {
+    static SEPARATIONS: OnceLock<(Vec<usize>, Vec<[u8; 8]>)> = OnceLock::new();
+    SEPARATIONS.get_or_init(|| {
+        let mut numbers = vec![usize::MAX; 256];
+        let mut placements = Vec::new();
+        for mask in (0..256usize).filter(|mask| mask.count_ones() == 4) {
+            numbers[mask] = placements.len();
+            let (mut top, mut bottom) = (0, 4);
+            placements.push(std::array::from_fn(|i| {
+                let piece = if mask & 1 << i != 0 {
+                    &mut top
+                } else {
+                    &mut bottom
+                };
+                *piece += 1;
+                *piece - 1
+            }));
+        }
+        (numbers, placements)
+    })
+}
@@ -0,0 +1,175 @@
+use crate::moves::MoveFace;
+use crate::puzzles::clock::ClockState;
+use crate::state::{CubeState, FACE_ORDER};
+use std::fmt::Write;
+
+/// Fill colour for the stickers of each face, as any SVG colour value. The
+/// values are escaped when written, so they cannot add markup to the SVG.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColourScheme {
+    pub up: String,
+    pub right: String,
+    pub front: String,
+    pub down: String,
+    pub left: String,
+    pub back: String,
+}
+
+impl Default for ColourScheme {
+    /// The WCA scheme: white top, green front.
+    fn default() -> Self {
+        ColourScheme {
+            up: "#ffffff".to_string(),
+            right: "#ff0000".to_string(),
+            front: "#00d800".to_string(),
+            down: "#ffff00".to_string(),
+            left: "#ff8c00".to_string(),
+            back: "#0000ff".to_string(),
+        }
+    }
+}
+
+impl ColourScheme {
+    pub fn colour(&self, face: MoveFace) -> &str {
+        match face {
+            MoveFace::Up => &self.up,
+            MoveFace::Right => &self.right,
+            MoveFace::Front => &self.front,
+            MoveFace::Down => &self.down,
+            MoveFace::Left => &self.left,
+            MoveFace::Back => &self.back,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SvgOptions {
+    /// Width and height of a single sticker in pixels.
+    pub sticker_size: u32,
+    /// Space left between neighbouring faces of the net in pixels.
+    pub face_gap: u32,
+    pub colours: ColourScheme,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            sticker_size: 20,
+            face_gap: 4,
+            colours: ColourScheme::default(),
+        }
+    }
+}
+
+/// Column and row of each face in the unfolded net: U above F, D below F, and
+/// L F R B from left to right.
+fn net_position(face: MoveFace) -> (u32, u32) {
+    match face {
+        MoveFace::Up => (1, 0),
+        MoveFace::Left => (0, 1),
+        MoveFace::Front => (1, 1),
+        MoveFace::Right => (2, 1),
+        MoveFace::Back => (3, 1),
+        MoveFace::Down => (1, 2),
+    }
+}
+
+/// `value` with the characters that could end an XML attribute or start
+/// markup replaced by their entities.
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Draws the state as an unfolded net.
+pub fn render_svg(state: &CubeState, options: &SvgOptions) -> String {
+    let size = state.size() as u32;
+    let face_size = size * options.sticker_size;
+    let step = face_size + options.face_gap;
+    let width = 4 * face_size + 5 * options.face_gap;
+    let height = 3 * face_size + 4 * options.face_gap;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+
+    for face in FACE_ORDER {
+        let (col, row) = net_position(face);
+        let x = options.face_gap + col * step;
+        let y = options.face_gap + row * step;
+        for (i, &sticker) in state.face(face).iter().enumerate() {
+            let i = i as u32;
+            let _ = writeln!(
+                svg,
+                r##"  <rect x="{}" y="{}" width="{s}" height="{s}" fill="{}" stroke="#000000" stroke-width="1"/>"##,
+                x + (i % size) * options.sticker_size,
+                y + (i / size) * options.sticker_size,
+                escape_attribute(options.colours.colour(sticker)),
+                s = options.sticker_size,
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draws both faces of a clock side by side, front on the left, each as seen
+/// from its own side. `sticker_size` sets the size of a dial and `face_gap`
+/// the space around the faces. Cube colours are not used.
+pub fn render_clock_svg(state: &ClockState, options: &SvgOptions) -> String {
+    let dial = options.sticker_size;
+    let face_size = 3 * dial;
+    let width = 2 * face_size + 3 * options.face_gap;
+    let height = face_size + 2 * options.face_gap;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+
+    for (i, (face, fill)) in [(state.front(), "#1c3f94"), (state.back(), "#8bb3e8")]
+        .into_iter()
+        .enumerate()
+    {
+        let x = options.face_gap + i as u32 * (face_size + options.face_gap);
+        let y = options.face_gap;
+        let _ = writeln!(
+            svg,
+            r##"  <rect x="{x}" y="{y}" width="{face_size}" height="{face_size}" fill="{fill}" stroke="#000000" stroke-width="1"/>"##
+        );
+        for (j, &hour) in face.iter().enumerate() {
+            let j = j as u32;
+            let radius = dial as f64 * 0.4;
+            let cx = (x + (j % 3) * dial) as f64 + dial as f64 / 2.0;
+            let cy = (y + (j / 3) * dial) as f64 + dial as f64 / 2.0;
+            let angle = hour as f64 * std::f64::consts::PI / 6.0;
+            let _ = writeln!(
+                svg,
+                r##"  <circle cx="{cx:.1}" cy="{cy:.1}" r="{radius:.1}" fill="#ffffff" stroke="#000000" stroke-width="1"/>"##
+            );
+            let _ = writeln!(
+                svg,
+                r##"  <line x1="{cx:.1}" y1="{cy:.1}" x2="{:.1}" y2="{:.1}" stroke="#d00000" stroke-width="2"/>"##,
+                cx + radius * 0.8 * angle.sin(),
+                cy - radius * 0.8 * angle.cos(),
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -1,23 +1,218 @@
+use crate::error::{MAX_SCRAMBLE_LENGTH, ScrambleError};
 use crate::generators;
-use crate::{cube::Cube, moves::Move};
+use crate::generators::cross::CrossOptions;
+use crate::{
+    cube::{self, Cube},
+    moves::Move,
+};
+use rand::{Rng, SeedableRng, rng};
+use rand_chacha::ChaCha8Rng;
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Scramble {
     pub moves: Vec<Move>,
 }
 
+/// The generator behind the `*_seeded` functions. The same seed always gives
+/// the same sequence of numbers, so the same seed, cube and length give the
+/// same scramble on a given version of this crate.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
 impl Scramble {
+    /// Like `try_generate`, but panics if the scramble cannot be generated.
     pub fn generate(cube: Cube, length: Option<usize>) -> Scramble {
-        match cube {
-            Cube::ThreeByThree => generators::three::generate(length),
-            Cube::FourByFour => generators::four::generate(length),
-            Cube::FiveByFive => generators::five::generate(length),
-            Cube::SixBySix => generators::six::generate(length),
-            Cube::SevenBySeven => generators::seven::generate(length),
-            _ => unimplemented!(),
+        Scramble::try_generate(cube, length).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_seeded(cube: Cube, length: Option<usize>, seed: u64) -> Scramble {
+        Scramble::try_generate_seeded(cube, length, seed).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_with_rng<R: Rng + ?Sized>(
+        cube: Cube,
+        length: Option<usize>,
+        rng: &mut R,
+    ) -> Scramble {
+        Scramble::try_generate_with_rng(cube, length, rng).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Generates a random-move scramble, or the default length for the cube
+    /// when `length` is `None`. Fails if `length` is zero or more than
+    /// MAX_SCRAMBLE_LENGTH.
+    pub fn try_generate(cube: Cube, length: Option<usize>) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_with_rng(cube, length, &mut rng())
+    }
+
+    pub fn try_generate_seeded(
+        cube: Cube,
+        length: Option<usize>,
+        seed: u64,
+    ) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_with_rng(cube, length, &mut seeded_rng(seed))
+    }
+
+    pub fn try_generate_with_rng<R: Rng + ?Sized>(
+        cube: Cube,
+        length: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Scramble, ScrambleError> {
+        if let Some(length) = length
+            && !(1..=MAX_SCRAMBLE_LENGTH).contains(&length)
+        {
+            return Err(ScrambleError::InvalidLength(length));
+        }
+
+        Ok(match cube::normalized(cube)? {
+            Cube::TwoByTwo => generators::two::generate_with_rng(rng, length),
+            Cube::ThreeByThree => generators::three::generate_with_rng(rng, length),
+            Cube::FourByFour => generators::four::generate_with_rng(rng, length),
+            Cube::FiveByFive => generators::five::generate_with_rng(rng, length),
+            Cube::SixBySix => generators::six::generate_with_rng(rng, length),
+            Cube::SevenBySeven => generators::seven::generate_with_rng(rng, length),
+            Cube::NxN(layers) => generators::nxn::generate_with_rng(rng, layers, length),
+        })
+    }
+
+    /// Like `try_generate_random_state`, but panics if the cube has no
+    /// random-state generator.
+    pub fn generate_random_state(cube: Cube) -> Scramble {
+        Scramble::try_generate_random_state(cube).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_random_state_seeded(cube: Cube, seed: u64) -> Scramble {
+        Scramble::try_generate_random_state_seeded(cube, seed)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_random_state_with_rng<R: Rng + ?Sized>(cube: Cube, rng: &mut R) -> Scramble {
+        Scramble::try_generate_random_state_with_rng(cube, rng)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Generates a scramble for a uniformly random state. Only the 2x2, 3x3
+    /// and 4x4 have random-state generators; other cubes give
+    /// `ScrambleError::RandomStateUnsupported` rather than a panic.
+    pub fn try_generate_random_state(cube: Cube) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_random_state_with_rng(cube, &mut rng())
+    }
+
+    pub fn try_generate_random_state_seeded(
+        cube: Cube,
+        seed: u64,
+    ) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_random_state_with_rng(cube, &mut seeded_rng(seed))
+    }
+
+    pub fn try_generate_random_state_with_rng<R: Rng + ?Sized>(
+        cube: Cube,
+        rng: &mut R,
+    ) -> Result<Scramble, ScrambleError> {
+        match cube::normalized(cube)? {
+            Cube::TwoByTwo => generators::two::try_generate_random_state_with_rng(rng, None),
+            Cube::ThreeByThree => Ok(generators::three::generate_random_state_with_rng(rng)),
+            Cube::FourByFour => Ok(generators::four::generate_random_state_with_rng(rng)),
+            _ => Err(ScrambleError::RandomStateUnsupported(cube)),
         }
     }
+
+    /// Like `try_generate_blind`, but panics if the cube has no blindfolded
+    /// generator.
+    pub fn generate_blind(cube: Cube) -> Scramble {
+        Scramble::try_generate_blind(cube).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_blind_seeded(cube: Cube, seed: u64) -> Scramble {
+        Scramble::try_generate_blind_seeded(cube, seed).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_blind_with_rng<R: Rng + ?Sized>(cube: Cube, rng: &mut R) -> Scramble {
+        Scramble::try_generate_blind_with_rng(cube, rng).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Generates a blindfolded scramble for the 3x3, 4x4 or 5x5: a normal
+    /// scramble followed by moves turning the cube to a uniformly random
+    /// orientation.
+    pub fn try_generate_blind(cube: Cube) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_blind_with_rng(cube, &mut rng())
+    }
+
+    pub fn try_generate_blind_seeded(cube: Cube, seed: u64) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_blind_with_rng(cube, &mut seeded_rng(seed))
+    }
+
+    pub fn try_generate_blind_with_rng<R: Rng + ?Sized>(
+        cube: Cube,
+        rng: &mut R,
+    ) -> Result<Scramble, ScrambleError> {
+        generators::blind::try_generate_with_rng(rng, cube::normalized(cube)?)
+    }
+
+    /// Like `try_generate_cross`, but panics if no scramble is found.
+    pub fn generate_cross(cross_length: usize, options: &CrossOptions) -> Scramble {
+        Scramble::try_generate_cross(cross_length, options)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_cross_seeded(
+        cross_length: usize,
+        options: &CrossOptions,
+        seed: u64,
+    ) -> Scramble {
+        Scramble::try_generate_cross_seeded(cross_length, options, seed)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn generate_cross_with_rng<R: Rng + ?Sized>(
+        cross_length: usize,
+        options: &CrossOptions,
+        rng: &mut R,
+    ) -> Scramble {
+        Scramble::try_generate_cross_with_rng(cross_length, options, rng)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Generates a 3x3 scramble whose optimal cross on the chosen colour, or
+    /// on every colour if none is chosen, is exactly `cross_length` moves,
+    /// from 1 up to 8, or 7 on every colour.
+    pub fn try_generate_cross(
+        cross_length: usize,
+        options: &CrossOptions,
+    ) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_cross_with_rng(cross_length, options, &mut rng())
+    }
+
+    pub fn try_generate_cross_seeded(
+        cross_length: usize,
+        options: &CrossOptions,
+        seed: u64,
+    ) -> Result<Scramble, ScrambleError> {
+        Scramble::try_generate_cross_with_rng(cross_length, options, &mut seeded_rng(seed))
+    }
+
+    pub fn try_generate_cross_with_rng<R: Rng + ?Sized>(
+        cross_length: usize,
+        options: &CrossOptions,
+        rng: &mut R,
+    ) -> Result<Scramble, ScrambleError> {
+        generators::cross::try_generate_with_rng(rng, cross_length, options)
+    }
+
+    /// A Fewest Moves scramble: `R' U' F`, a random-state 3x3 scramble that
+    /// does not cancel with it, and `R' U' F` again.
+    pub fn generate_fmc() -> Scramble {
+        Scramble::generate_fmc_with_rng(&mut rng())
+    }
+
+    pub fn generate_fmc_seeded(seed: u64) -> Scramble {
+        Scramble::generate_fmc_with_rng(&mut seeded_rng(seed))
+    }
+
+    pub fn generate_fmc_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Scramble {
+        generators::three::generate_fmc_with_rng(rng)
+    }
 }
 
 impl fmt::Display for Scramble {
@@ -0,0 +1,121 @@
+use crate::solvers::three::cubie::{CubieCube, EDGES};
+use std::sync::OnceLock;
+
+// The edges of each face's cross in Kociemba order, for the faces U, R, F, D,
+// L and B.
+const CROSS_EDGES: [[usize; 4]; 6] = [
+    [0, 1, 2, 3],
+    [0, 4, 8, 11],
+    [1, 5, 8, 9],
+    [4, 5, 6, 7],
+    [2, 6, 9, 10],
+    [3, 7, 10, 11],
+];
+
+/// Each cross edge's position and orientation as `2 * position +
+/// orientation`, a digit in base 24, with the first cross edge the least
+/// significant. Some indices put two edges in one place and are never
+/// reached.
+const STATES: usize = 24 * 24 * 24 * 24;
+
+/// Every cross is solved in at most this many moves.
+pub const MAX_DEPTH: usize = 8;
+
+/// Fewest outer layer moves to solve the cross on `face`, i.e. its four
+/// edges in place and oriented. Faces are numbered U, R, F, D, L, B.
+pub(crate) fn distance(cube: &CubieCube, face: usize) -> usize {
+    distances(face)[index(cube, face)] as usize
+}
+
+/// An optimal solution to the cross on `face`, as moves indexed `face * 3 +
+/// quarter turns - 1`.
+pub(crate) fn solve(cube: &CubieCube, face: usize) -> Vec<usize> {
+    let distances = distances(face);
+    let mut state = index(cube, face);
+    let mut solution = Vec::new();
+    // Every state but the solved one has a move bringing it a move closer.
+    while distances[state] > 0 {
+        let (m, moved) = moves()
+            .iter()
+            .map(|targets| turn(state, targets))
+            .enumerate()
+            .find(|&(_, moved)| distances[moved] < distances[state])
+            .expect("a move brings the cross closer to solved");
+        solution.push(m);
+        state = moved;
+    }
+    solution
+}
+
+fn distances(face: usize) -> &'static [u8] {
+    static DISTANCES: [OnceLock<Vec<u8>>; 6] = [const { OnceLock::new() }; 6];
+    DISTANCES[face].get_or_init(|| build_distances(face))
+}
+
+fn index(cube: &CubieCube, face: usize) -> usize {
+    let cross_edges = &CROSS_EDGES[face];
+    let mut digits = [0; 4];
+    for position in 0..EDGES {
+        if let Some(edge) = cross_edges
+            .iter()
+            .position(|&edge| edge == cube.ep[position] as usize)
+        {
+            digits[edge] = 2 * position + cube.eo[position] as usize;
+        }
+    }
+    digits
+        .iter()
+        .rev()
+        .fold(0, |index, digit| index * 24 + digit)
+}
+
+/// `moves()[m][digit]` is where move `m`, indexed as `face * 3 + quarter
+/// turns - 1`, takes an edge with that digit.
+fn moves() -> &'static [[u8; 24]; 18] {
+    static MOVES: OnceLock<[[u8; 24]; 18]> = OnceLock::new();
+    MOVES.get_or_init(|| {
+        let mut moves = [[0; 24]; 18];
+        for (m, targets) in moves.iter_mut().enumerate() {
+            let turned = CubieCube::SOLVED.turn(m / 3, m % 3 + 1);
+            for position in 0..EDGES {
+                // The edge now at `position` came from `turned.ep[position]`.
+                let from = turned.ep[position] as usize;
+                for orientation in 0..2 {
+                    let flipped = (orientation + turned.eo[position]) % 2;
+                    targets[2 * from + orientation as usize] = (2 * position) as u8 + flipped;
+                }
+            }
+        }
+        moves
+    })
+}
+
+fn build_distances(face: usize) -> Vec<u8> {
+    let mut distance = vec![u8::MAX; STATES];
+    let solved = index(&CubieCube::SOLVED, face);
+    distance[solved] = 0;
+    let mut frontier = vec![solved];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for state in frontier {
+            for targets in moves() {
+                let moved = turn(state, targets);
+                if distance[moved] == u8::MAX {
+                    distance[moved] = depth;
+                    next.push(moved);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distance
+}
+
+/// The index after a move taking each digit to `targets[digit]`.
+fn turn(index: usize, targets: &[u8; 24]) -> usize {
+    (0..4).rev().fold(0, |moved, edge| {
+        moved * 24 + targets[index / 24usize.pow(edge as u32) % 24] as usize
+    })
+}
@@ -0,0 +1,134 @@
+/// Moves the bits of a mask over the 24 wing or centre positions the way a
+/// move moves the pieces, a byte at a time.
+pub(crate) struct MaskPermutation {
+    bytes: [[u32; 256]; 3],
+}
+
+impl MaskPermutation {
+    /// `targets[p]` is where the piece at position `p` goes.
+    pub fn new(targets: &[u8; 24]) -> MaskPermutation {
+        let mut bytes = [[0; 256]; 3];
+        for (chunk, table) in bytes.iter_mut().enumerate() {
+            for (value, mask) in table.iter_mut().enumerate() {
+                for bit in 0..8 {
+                    if value >> bit & 1 == 1 {
+                        *mask |= 1 << targets[chunk * 8 + bit];
+                    }
+                }
+            }
+        }
+        MaskPermutation { bytes }
+    }
+
+    pub fn apply(&self, mask: u32) -> u32 {
+        self.bytes[0][(mask & 0xff) as usize]
+            | self.bytes[1][(mask >> 8 & 0xff) as usize]
+            | self.bytes[2][(mask >> 16) as usize]
+    }
+}
+
+const fn binomials() -> [[usize; 25]; 25] {
+    let mut table = [[0; 25]; 25];
+    let mut n = 0;
+    while n < 25 {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+}
+
+const BINOMIALS: [[usize; 25]; 25] = binomials();
+
+/// Number of masks over 24 positions with `bits` bits set.
+pub(crate) const fn subsets(bits: usize) -> usize {
+    BINOMIALS[24][bits]
+}
+
+/// `BYTE_RANKS[chunk][seen][byte]` is what the bits of `byte` add to the
+/// rank of a mask when they are its `chunk`th byte, after `seen` set bits.
+const fn byte_ranks() -> [[[u32; 256]; 25]; 3] {
+    let mut table = [[[0; 256]; 25]; 3];
+    let mut chunk = 0;
+    while chunk < 3 {
+        let mut seen = 0;
+        while seen < 25 {
+            let mut byte = 0;
+            while byte < 256 {
+                let (mut rank, mut count) = (0, seen);
+                let mut bit = 0;
+                while bit < 8 {
+                    if byte >> bit & 1 == 1 && count < 24 {
+                        count += 1;
+                        rank += BINOMIALS[chunk * 8 + bit][count];
+                    }
+                    bit += 1;
+                }
+                table[chunk][seen][byte] = rank as u32;
+                byte += 1;
+            }
+            seen += 1;
+        }
+        chunk += 1;
+    }
+    table
+}
+
+static BYTE_RANKS: [[[u32; 256]; 25]; 3] = byte_ranks();
+
+/// Index of a mask among those with as many bits set, from 0 to
+/// `subsets(bits) - 1`.
+pub(crate) fn subset_rank(mask: u32) -> usize {
+    let low = (mask & 0xff) as usize;
+    let middle = (mask >> 8 & 0xff) as usize;
+    let seen = low.count_ones() as usize;
+    (BYTE_RANKS[0][0][low]
+        + BYTE_RANKS[1][seen][middle]
+        + BYTE_RANKS[2][seen + middle.count_ones() as usize][mask as usize >> 16]) as usize
+}
+
+/// Every mask over 24 positions with `bits` bits set, in order of
+/// `subset_rank`, which is numeric order.
+pub(crate) fn masks(bits: usize) -> Vec<u32> {
+    let mut masks = Vec::with_capacity(subsets(bits));
+    let mut mask = (1u32 << bits) - 1;
+    while mask < 1 << 24 {
+        masks.push(mask);
+        // The next larger number with as many bits set.
+        let lowest = mask & mask.wrapping_neg();
+        let carried = mask + lowest;
+        mask = carried | (((mask ^ carried) >> 2) / lowest);
+    }
+    masks
+}
+
+/// Index of a permutation of `0..n` among all `n!` of them.
+pub(crate) fn permutation_rank(permutation: &[u8]) -> usize {
+    let mut rank = 0;
+    for (i, &value) in permutation.iter().enumerate() {
+        let smaller_after = permutation[i + 1..]
+            .iter()
+            .filter(|&&other| other < value)
+            .count();
+        rank = rank * (permutation.len() - i) + smaller_after;
+    }
+    rank
+}
+
+/// The permutation of `0..n` with index `rank`, see `permutation_rank`.
+pub(crate) fn permutation_from_rank(mut rank: usize, n: usize) -> Vec<u8> {
+    let mut digits = vec![0; n];
+    for i in (0..n).rev() {
+        digits[i] = rank % (n - i);
+        rank /= n - i;
+    }
+    let mut unused: Vec<u8> = (0..n as u8).collect();
+    digits
+        .into_iter()
+        .map(|digit| unused.remove(digit))
+        .collect()
+}
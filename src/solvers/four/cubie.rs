@@ -0,0 +1,326 @@
+use crate::cube::Cube;
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::solvers::three::cubie::{CORNERS, CubieCube, EDGES};
+use crate::state::{CubeState, FACE_ORDER};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::sync::OnceLock;
+
+pub(crate) const WINGS: usize = 24;
+pub(crate) const CENTRES: usize = 24;
+
+/// Every face turned by one, two or three quarter turns, first the outer
+/// layer and then two layers: U, U2, U', Uw, Uw2, Uw', R, ...
+pub(crate) const MOVES: usize = 36;
+
+const TYPES: [MoveType; 3] = [MoveType::Normal, MoveType::Double, MoveType::Prime];
+
+/// The move with index `m`, with faces ordered U, R, F, D, L, B.
+pub(crate) fn move_of(m: usize) -> Move {
+    Move {
+        move_face: FACE_ORDER[m / 6],
+        move_type: TYPES[m % 3],
+        move_width: if m % 6 < 3 {
+            MoveWidth::Single
+        } else {
+            MoveWidth::Wide
+        },
+    }
+}
+
+/// The index of an outer layer or wide move, see `move_of`.
+pub(crate) fn move_index(move_: &Move) -> usize {
+    let face = FACE_ORDER
+        .iter()
+        .position(|face| *face == move_.move_face)
+        .expect("every face is in FACE_ORDER");
+    let turns = TYPES
+        .iter()
+        .position(|move_type| *move_type == move_.move_type)
+        .expect("every move type is in TYPES");
+    let wide = match move_.move_width {
+        MoveWidth::Single => 0,
+        MoveWidth::Wide => 1,
+        _ => panic!("4x4 state only supports single-layer and wide moves"),
+    };
+    face * 6 + wide * 3 + turns
+}
+
+/// Where each piece goes under each move, worked out from the stickers of a
+/// 4x4 `CubeState`.
+///
+/// Wings are the 24 edge pieces and are numbered by the sticker of theirs
+/// on U or D, or F or B for the ones in the middle layer, in sticker order.
+/// A wing cannot be flipped in place, so its position says everything about
+/// it. Centres are numbered by sticker, so centre `c` belongs to face
+/// `FACE_ORDER[c / 4]`.
+pub(crate) struct Geometry {
+    /// `wing_moves[m][p]` is where move `m` takes the wing at position `p`.
+    pub wing_moves: [[u8; WINGS]; MOVES],
+    pub centre_moves: [[u8; CENTRES]; MOVES],
+    /// The 3x3 edge, in Kociemba order, each wing position is part of. A
+    /// wing's first sticker is the one the 3x3 orients the edge by.
+    pub wing_edges: [u8; WINGS],
+    /// Whether the wing from position `w` shows its first sticker on the
+    /// first sticker of position `p`, as `wing_faces[w][p]`. Turning a wing
+    /// into a position always brings the same sticker to the same face.
+    pub wing_faces: [[bool; WINGS]; WINGS],
+    /// Where the x and z rotations take the centre at each position. They
+    /// bring the F and B centres, and the L and R ones, onto U and D.
+    pub centre_rotations: [[u8; CENTRES]; 2],
+}
+
+pub(crate) fn geometry() -> &'static Geometry {
+    static GEOMETRY: OnceLock<Geometry> = OnceLock::new();
+    GEOMETRY.get_or_init(build_geometry)
+}
+
+// The 3x3 edges in Kociemba order, each by the face the 3x3 orients it by
+// and the other face.
+const EDGE_FACES: [(MoveFace, MoveFace); EDGES] = [
+    (MoveFace::Up, MoveFace::Right),
+    (MoveFace::Up, MoveFace::Front),
+    (MoveFace::Up, MoveFace::Left),
+    (MoveFace::Up, MoveFace::Back),
+    (MoveFace::Down, MoveFace::Right),
+    (MoveFace::Down, MoveFace::Front),
+    (MoveFace::Down, MoveFace::Left),
+    (MoveFace::Down, MoveFace::Back),
+    (MoveFace::Front, MoveFace::Right),
+    (MoveFace::Front, MoveFace::Left),
+    (MoveFace::Back, MoveFace::Left),
+    (MoveFace::Back, MoveFace::Right),
+];
+
+fn build_geometry() -> Geometry {
+    let state = CubeState::new(Cube::FourByFour);
+    let stickers = 6 * 16;
+    let outer = |position: [i32; 3]| position.iter().filter(|c| c.abs() == 3).count();
+
+    // Each wing's stickers, the one on U or D (else F or B) first.
+    let mut wings: Vec<[usize; 2]> = Vec::new();
+    let mut centres = Vec::new();
+    for index in 0..stickers {
+        let position = state.sticker_position(index).1;
+        match outer(position) {
+            1 => centres.push(index),
+            2 if !wings.iter().any(|wing| wing.contains(&index)) => {
+                let other = (0..stickers)
+                    .find(|&other| other != index && state.sticker_position(other).1 == position)
+                    .expect("every wing has two stickers");
+                let rank = |i: usize| face_rank(state.sticker_position(i).0);
+                let (first, second) = if rank(index) < rank(other) {
+                    (index, other)
+                } else {
+                    (other, index)
+                };
+                wings.push([first, second]);
+            }
+            _ => {}
+        }
+    }
+    wings.sort_unstable();
+    let wing_at = |sticker: usize| {
+        wings
+            .iter()
+            .position(|wing| wing.contains(&sticker))
+            .expect("a wing sticker stays on a wing")
+    };
+
+    let mut geometry = Geometry {
+        wing_moves: [[0; WINGS]; MOVES],
+        centre_moves: [[0; CENTRES]; MOVES],
+        wing_edges: [0; WINGS],
+        wing_faces: [[false; WINGS]; WINGS],
+        centre_rotations: [[0; CENTRES]; 2],
+    };
+    let centre_targets = |move_: &Move| {
+        let target = state.sticker_targets(move_);
+        std::array::from_fn(|c| {
+            centres
+                .iter()
+                .position(|&other| other == target[centres[c]])
+                .expect("a centre sticker stays on a centre") as u8
+        })
+    };
+    // Where each sticker goes, for finding every place a wing's first
+    // sticker can be turned to.
+    let mut targets = Vec::with_capacity(MOVES);
+    for m in 0..MOVES {
+        let target = state.sticker_targets(&move_of(m));
+        for (p, wing) in wings.iter().enumerate() {
+            geometry.wing_moves[m][p] = wing_at(target[wing[0]]) as u8;
+        }
+        geometry.centre_moves[m] = centre_targets(&move_of(m));
+        targets.push(target);
+    }
+    for (rotation, face) in [MoveFace::Right, MoveFace::Front].into_iter().enumerate() {
+        geometry.centre_rotations[rotation] = centre_targets(&Move {
+            move_face: face,
+            move_type: MoveType::Normal,
+            move_width: MoveWidth::Rotation,
+        });
+    }
+
+    for (w, wing) in wings.iter().enumerate() {
+        let mut reached = vec![false; stickers];
+        let mut frontier = vec![wing[0]];
+        reached[wing[0]] = true;
+        while let Some(sticker) = frontier.pop() {
+            for target in &targets {
+                if !reached[target[sticker]] {
+                    reached[target[sticker]] = true;
+                    frontier.push(target[sticker]);
+                }
+            }
+        }
+        for (p, other) in wings.iter().enumerate() {
+            assert!(
+                reached[other[0]] != reached[other[1]],
+                "a wing reaches every position in one way"
+            );
+            geometry.wing_faces[w][p] = reached[other[0]];
+        }
+
+        let faces = wing.map(|sticker| state.sticker_position(sticker).0);
+        geometry.wing_edges[w] = EDGE_FACES
+            .iter()
+            .position(|&(first, second)| faces == [first, second])
+            .expect("every wing is on a 3x3 edge, first sticker first")
+            as u8;
+    }
+    geometry
+}
+
+/// A whole-cube rotation. A 4x4 has no fixed centres to tell which way up it
+/// is, so a cube can be solved as seen from any side.
+pub(crate) struct Rotation {
+    /// The solved cube turned back by the rotation.
+    unturned: FourCube,
+    /// The rotation as wide moves, each with the opposite wide move.
+    moves: Vec<usize>,
+    /// `original[m]` is the move that `m` made on the rotated cube is on the
+    /// cube itself.
+    pub original: [usize; MOVES],
+}
+
+/// The 24 rotations, the identity first.
+pub(crate) fn rotations() -> &'static [Rotation] {
+    static ROTATIONS: OnceLock<Vec<Rotation>> = OnceLock::new();
+    ROTATIONS.get_or_init(build_rotations)
+}
+
+fn build_rotations() -> Vec<Rotation> {
+    let wide = |face: usize, quarter_turns: usize| face * 6 + 3 + quarter_turns - 1;
+    // x, y and z as Rw Lw', Uw Dw' and Fw Bw'.
+    let [x, y, z] = [
+        [wide(1, 1), wide(4, 3)],
+        [wide(0, 1), wide(3, 3)],
+        [wide(2, 1), wide(5, 3)],
+    ];
+    let apply = |cube: FourCube, moves: &[usize]| moves.iter().fold(cube, |cube, &m| cube.apply(m));
+
+    // Each axis onto U and D, then each quarter turn about it.
+    let axes = [0, 1, 2, 3].map(|times| x.repeat(times)).into_iter();
+    let axes = axes.chain([1, 3].map(|times| z.repeat(times)));
+    axes.flat_map(|axis| (0..4).map(move |times| [axis.clone(), y.repeat(times)].concat()))
+        .map(|moves| {
+            let inverse: Vec<usize> = moves.iter().rev().map(|&m| m + 2 - 2 * (m % 3)).collect();
+            let original = std::array::from_fn(|m| {
+                let turned = apply(apply(apply(FourCube::SOLVED, &moves), &[m]), &inverse);
+                (0..MOVES)
+                    .find(|&original| FourCube::SOLVED.apply(original) == turned)
+                    .expect("a move made on a rotated cube is a move")
+            });
+            Rotation {
+                unturned: apply(FourCube::SOLVED, &inverse),
+                moves,
+                original,
+            }
+        })
+        .collect()
+}
+
+/// Faces in the order a wing's first sticker is chosen: U and D, then F and
+/// B, as the 3x3 orients its edges.
+fn face_rank(face: MoveFace) -> usize {
+    match face {
+        MoveFace::Up | MoveFace::Down => 0,
+        MoveFace::Front | MoveFace::Back => 1,
+        MoveFace::Left | MoveFace::Right => 2,
+    }
+}
+
+/// Piece-level description of a 4x4: the corners as on a 3x3 (whose edges
+/// are unused), the wing from position `wings[p]` at each position `p` and
+/// the colour, as an index into FACE_ORDER, of each centre.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct FourCube {
+    pub corners: CubieCube,
+    pub wings: [u8; WINGS],
+    pub centres: [u8; CENTRES],
+}
+
+impl FourCube {
+    pub const SOLVED: FourCube = FourCube {
+        corners: CubieCube::SOLVED,
+        wings: [
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+        ],
+        centres: [
+            0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5,
+        ],
+    };
+
+    /// Uniformly random state, counting states that differ only by the
+    /// colours of identical centres as one. Every arrangement of the corners
+    /// with a valid twist and every arrangement of the wings and centres can
+    /// be reached.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> FourCube {
+        let mut cube = FourCube::SOLVED;
+        cube.corners.cp.shuffle(rng);
+        for i in 0..CORNERS - 1 {
+            cube.corners.co[i] = rng.random_range(0..3);
+        }
+        cube.corners.co[CORNERS - 1] =
+            (3 - cube.corners.co[..CORNERS - 1].iter().sum::<u8>() % 3) % 3;
+        cube.wings.shuffle(rng);
+        cube.centres.shuffle(rng);
+        cube
+    }
+
+    pub fn apply(&self, m: usize) -> FourCube {
+        let geometry = geometry();
+        let mut cube = *self;
+        cube.corners = self.corners.turn(m / 6, m % 3 + 1);
+        // The turn moves the unused 3x3 edges too. Keeping them solved lets
+        // equal 4x4 states compare equal.
+        cube.corners.ep = CubieCube::SOLVED.ep;
+        cube.corners.eo = CubieCube::SOLVED.eo;
+        for p in 0..WINGS {
+            cube.wings[geometry.wing_moves[m][p] as usize] = self.wings[p];
+        }
+        for c in 0..CENTRES {
+            cube.centres[geometry.centre_moves[m][c] as usize] = self.centres[c];
+        }
+        cube
+    }
+
+    /// The cube as seen after `rotation`, with its colours renamed so that it
+    /// is solved when the cube is. Its solutions are the cube's, with each
+    /// move `m` made as `rotation.original[m]`.
+    pub fn rotated(&self, rotation: &Rotation) -> FourCube {
+        let unturned = rotation.unturned;
+        let mut cube = unturned;
+        cube.corners = unturned.corners.multiply(&self.corners);
+        for p in 0..WINGS {
+            cube.wings[p] = unturned.wings[self.wings[p] as usize];
+        }
+        // Every face of `unturned` has one colour, so a centre's colour says
+        // which face it comes from.
+        for c in 0..CENTRES {
+            cube.centres[c] = unturned.centres[4 * self.centres[c] as usize];
+        }
+        rotation.moves.iter().fold(cube, |cube, &m| cube.apply(m))
+    }
+}
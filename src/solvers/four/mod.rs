@@ -0,0 +1,64 @@
+mod coordinates;
+mod cubie;
+mod search;
+mod tables;
+
+use crate::moves::Move;
+use crate::scramble::Scramble;
+use cubie::{FourCube, move_index, move_of};
+use rand::Rng;
+
+/// Piece permutation of a 4x4, in the fixed orientation with the U centres
+/// on top and the F centres in front. Centres of one colour are
+/// interchangeable, so only their colours are kept.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FourByFourState {
+    cube: FourCube,
+}
+
+impl Default for FourByFourState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl FourByFourState {
+    pub fn solved() -> FourByFourState {
+        FourByFourState {
+            cube: FourCube::SOLVED,
+        }
+    }
+
+    /// Picks a state uniformly at random from every reachable 4x4 state.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> FourByFourState {
+        FourByFourState {
+            cube: FourCube::random(rng),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.cube == FourCube::SOLVED
+    }
+
+    /// Applies an outer layer or wide move. Slice moves and rotations would
+    /// move the centres out of the fixed orientation and are rejected.
+    pub fn apply_move(&mut self, move_: &Move) {
+        self.cube = self.cube.apply(move_index(move_));
+    }
+
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for move_ in moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Solves the state by reduction: four phases pair the centres and edges
+    /// into a 3x3 with solvable parity and oriented pieces, typically in
+    /// about 41 moves, and the two-phase solver finishes it with its second
+    /// phase alone, in about 14. Moves that cancel where the stages meet are
+    /// merged, leaving about 54 moves on average.
+    pub fn solve(&self) -> Vec<Move> {
+        let moves = search::solve(&self.cube).into_iter().map(move_of).collect();
+        Scramble { moves }.simplify().moves
+    }
+}
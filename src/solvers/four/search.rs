@@ -0,0 +1,273 @@
+use super::coordinates::subset_rank;
+use super::cubie::{FourCube, geometry, move_index, rotations};
+use super::tables::{PHASE1_MOVES, PHASE2_MOVES, PHASE3_MOVES, PHASE4_MOVES, Tables, mask, tables};
+use crate::solvers::three::coordinates::SLICES;
+use crate::solvers::three::cubie::{CubieCube, EDGES, permutation_parity};
+use crate::solvers::three::{DEFAULT_MAX_LENGTH, ThreeByThreeState};
+
+/// Reduction in four phases, each an IDA* search within a smaller subgroup:
+///
+/// 1. Every centre onto its axis, with the wings in an even permutation.
+///    After this, outer layer turns and wide half turns are enough.
+/// 2. The wings into their classes, with the centres in an arrangement
+///    phase 3 can bring to one phase 4 can solve. After this, R and L
+///    quarter turns are not needed.
+/// 3. The middle layer edges' wings into the middle layer and the corners
+///    oriented, with centres and edge pairing phase 4 can solve. After
+///    this, U and D turns and half turns are enough.
+/// 4. Solve the centres and pair the edges, with the edges in a permutation
+///    of the same parity as the corners.
+///
+/// The reduced cube is then a 3x3 in the subgroup <U, D, R2, L2, F2, B2>,
+/// which the two-phase solver finishes with its second phase alone.
+///
+/// Phase 1 looks the same from every side, but the later phases favour some
+/// axes over others, so they solve the cube as seen from whichever rotation
+/// gives the shortest phase 2 and the lowest phase 3 bound after it. That
+/// saves about one move on average.
+///
+/// Returns the moves, indexed as in `move_of`.
+pub(crate) fn solve(cube: &FourCube) -> Vec<usize> {
+    let tables = tables();
+    let apply =
+        |cube: &FourCube, moves: &[usize]| moves.iter().fold(*cube, |cube, &m| cube.apply(m));
+
+    let first = phase1(tables, cube);
+    let cube = apply(cube, &first);
+    let (rotation, second, cube) = rotations()
+        .iter()
+        .map(|rotation| {
+            let cube = cube.rotated(rotation);
+            let moves = phase2(tables, &cube);
+            let reached = apply(&cube, &moves);
+            (rotation, moves, reached)
+        })
+        .min_by_key(|(_, moves, cube)| {
+            moves.len() + phase3_bound(tables, phase3_start(tables, cube))
+        })
+        .expect("there is a rotation");
+    let third = phase3(tables, &cube);
+    let cube = apply(&cube, &third);
+    let fourth = phase4(tables, &cube);
+    let cube = apply(&cube, &fourth);
+    let finish = ThreeByThreeState::from_cubie(to_three(tables, &cube))
+        .solve(DEFAULT_MAX_LENGTH)
+        .expect("phase 2 of the two-phase search solves a reduced cube in 18 moves");
+
+    let rest = [second, third, fourth].concat();
+    let rest = rest.into_iter().chain(finish.iter().map(move_index));
+    first
+        .into_iter()
+        .chain(rest.map(|m| rotation.original[m]))
+        .collect()
+}
+
+/// The positions of the U and D, F and B, and L and R centres, and the
+/// parity of the wings.
+fn centre_axes(cube: &FourCube) -> ([u32; 3], bool) {
+    (
+        [0, 2, 1].map(|axis| mask(|c| cube.centres[c] as usize % 3 == axis)),
+        permutation_parity(&cube.wings),
+    )
+}
+
+fn phase1(tables: &Tables, cube: &FourCube) -> Vec<usize> {
+    search(
+        centre_axes(cube),
+        &PHASE1_MOVES,
+        |(axes, parity), m| {
+            (
+                axes.map(|centres| tables.centre_moves[m].apply(centres)),
+                parity != tables.wing_parity_flips[m],
+            )
+        },
+        |([ud, fb, lr], parity)| {
+            let distance = |centres: u32| {
+                tables.centre_axis_distance[2 * subset_rank(centres) + parity as usize]
+            };
+            distance(ud)
+                .max(distance(tables.centre_rotations[0].apply(fb)))
+                .max(distance(tables.centre_rotations[1].apply(lr))) as usize
+        },
+        |_| true,
+    )
+}
+
+fn phase2(tables: &Tables, cube: &FourCube) -> Vec<usize> {
+    let start = (
+        mask(|p| tables.class_mask >> cube.wings[p] & 1 == 1),
+        mask(|c| cube.centres[c] < 3),
+    );
+    search(
+        start,
+        &PHASE2_MOVES,
+        |(class, centres), i| {
+            let m = PHASE2_MOVES[i];
+            (
+                tables.wing_moves[m].apply(class),
+                tables.centre_moves[m].apply(centres),
+            )
+        },
+        |(class, centres)| {
+            tables.wing_class_distance[subset_rank(class)]
+                .max(tables.centre_orbit_distance[subset_rank(centres)]) as usize
+        },
+        |_| true,
+    )
+}
+
+/// The corner twist, each class's middle layer edge wings as a 3x3 slice
+/// coordinate, and the positions of the U, R and F centres.
+type Phase3 = (usize, [usize; 2], u32);
+
+fn phase3_start(tables: &Tables, cube: &FourCube) -> Phase3 {
+    let geometry = geometry();
+    // Each class's wings in their edges' places, to reuse the 3x3 slice
+    // coordinate.
+    let slice = |positions: &[u8; EDGES]| {
+        let mut edges = CubieCube::SOLVED;
+        edges.ep = positions.map(|p| geometry.wing_edges[cube.wings[p as usize] as usize]);
+        edges.slice()
+    };
+    (
+        cube.corners.twist(),
+        [&tables.class_positions, &tables.other_positions].map(slice),
+        mask(|c| cube.centres[c] < 3),
+    )
+}
+
+fn phase3_bound(tables: &Tables, (twist, slices, centres): Phase3) -> usize {
+    let twist_slice =
+        |class: usize| tables.twist_slice_distance[class][twist * SLICES + slices[class]];
+    tables.slice_distance[slices[0] * SLICES + slices[1]]
+        .max(twist_slice(0))
+        .max(twist_slice(1))
+        .max(tables.centre_coset_distance[subset_rank(centres)]) as usize
+}
+
+fn phase3(tables: &Tables, cube: &FourCube) -> Vec<usize> {
+    search(
+        phase3_start(tables, cube),
+        &PHASE3_MOVES,
+        |(twist, slices, centres), i| {
+            let m = PHASE3_MOVES[i];
+            (
+                tables.twist_moves[twist][m] as usize,
+                [0, 1].map(|class| tables.slice_moves[class][slices[class]][i] as usize),
+                tables.centre_moves[m].apply(centres),
+            )
+        },
+        |state| phase3_bound(tables, state),
+        // Which edges' wings end up together decides whether phase 4 can pair
+        // them, and that is only known with the whole cube.
+        |moves| {
+            let cube = moves.iter().fold(*cube, |cube, &m| cube.apply(m));
+            tables.pairing_distance[tables.pairing(&cube)] != u8::MAX
+        },
+    )
+}
+
+fn phase4(tables: &Tables, cube: &FourCube) -> Vec<usize> {
+    let centres = tables
+        .centre_states
+        .binary_search(&mask(|c| cube.centres[c] < 3))
+        .expect("phase 3 leaves centres phase 4 can solve");
+    search(
+        (centres, tables.pairing(cube)),
+        &PHASE4_MOVES,
+        |(centres, pairing), i| {
+            (
+                tables.centre_state_moves[centres][i] as usize,
+                tables.pairing_move(pairing, i),
+            )
+        },
+        |(centres, pairing)| {
+            // The pairing ends with the middle layer part and the parity.
+            let slice = pairing % 48;
+            tables.centre_distance[centres]
+                .max(tables.pairing_distance[pairing])
+                .max(tables.centre_slice_distance[48 * centres + slice]) as usize
+        },
+        |_| true,
+    )
+}
+
+/// The 3x3 a reduced cube turns like: its corners, and each edge as the
+/// wing in `class_positions` there.
+fn to_three(tables: &Tables, cube: &FourCube) -> CubieCube {
+    let geometry = geometry();
+    let mut three = cube.corners;
+    for edge in 0..EDGES {
+        let p = tables.class_positions[edge] as usize;
+        let wing = cube.wings[p] as usize;
+        three.ep[edge] = geometry.wing_edges[wing];
+        three.eo[edge] = !geometry.wing_faces[wing][p] as u8;
+    }
+    three
+}
+
+/// Moves on one axis commute, so only one order of them is searched: faces
+/// in FACE_ORDER, outer layer before wide.
+fn redundant(previous: usize, m: usize) -> bool {
+    let (turned, previous) = (m / 3, previous / 3);
+    turned / 2 % 3 == previous / 2 % 3 && turned <= previous
+}
+
+/// Iterative deepening A* from `start` to a state `bound` puts at 0 that the
+/// moves leading to it `accept`. `bound` must never overestimate the
+/// distance to such a state, and `next(state, i)` is the state after
+/// `moves[i]`.
+fn search<S: Copy>(
+    start: S,
+    moves: &[usize],
+    next: impl Fn(S, usize) -> S,
+    bound: impl Fn(S) -> usize,
+    accept: impl Fn(&[usize]) -> bool,
+) -> Vec<usize> {
+    let mut search = Search {
+        moves,
+        next: &next,
+        bound: &bound,
+        accept: &accept,
+        solution: Vec::new(),
+    };
+    for depth in bound(start).. {
+        if search.descend(start, depth, None) {
+            return search.solution;
+        }
+    }
+    unreachable!("every depth is searched until a solution is found")
+}
+
+struct Search<'a, S> {
+    moves: &'a [usize],
+    next: &'a dyn Fn(S, usize) -> S,
+    bound: &'a dyn Fn(S) -> usize,
+    accept: &'a dyn Fn(&[usize]) -> bool,
+    solution: Vec<usize>,
+}
+
+impl<S: Copy> Search<'_, S> {
+    fn descend(&mut self, state: S, depth: usize, previous: Option<usize>) -> bool {
+        if depth == 0 {
+            return (self.bound)(state) == 0 && (self.accept)(&self.solution);
+        }
+
+        for (i, &m) in self.moves.iter().enumerate() {
+            if previous.is_some_and(|previous| redundant(previous, m)) {
+                continue;
+            }
+            let next = (self.next)(state, i);
+            if (self.bound)(next) >= depth {
+                continue;
+            }
+
+            self.solution.push(m);
+            if self.descend(next, depth - 1, Some(m)) {
+                return true;
+            }
+            self.solution.pop();
+        }
+        false
+    }
+}
@@ -0,0 +1,431 @@
+use super::coordinates::{
+    MaskPermutation, masks, permutation_from_rank, permutation_rank, subset_rank,
+};
+use super::cubie::{CENTRES, FourCube, MOVES, WINGS, geometry};
+use crate::solvers::three::coordinates::{SLICES, TWISTS};
+use crate::solvers::three::cubie::{CubieCube, EDGES, permutation_parity};
+use std::sync::OnceLock;
+
+/// Moves `m` are indexed as `face * 6 + wide * 3 + quarter_turns - 1`, see
+/// `move_of`. Phase 1 uses all of them.
+pub(crate) const PHASE1_MOVES: [usize; MOVES] = {
+    let mut moves = [0; MOVES];
+    let mut m = 0;
+    while m < MOVES {
+        moves[m] = m;
+        m += 1;
+    }
+    moves
+};
+
+/// Outer layer turns and wide half turns, which keep every centre on its
+/// axis.
+pub(crate) const PHASE2_MOVES: [usize; 24] = [
+    0, 1, 2, 4, 6, 7, 8, 10, 12, 13, 14, 16, 18, 19, 20, 22, 24, 25, 26, 28, 30, 31, 32, 34,
+];
+
+/// U, D, F and B turns, R and L half turns and wide half turns, which also
+/// keep the wings of each class together.
+pub(crate) const PHASE3_MOVES: [usize; 20] = [
+    0, 1, 2, 4, 7, 10, 12, 13, 14, 16, 18, 19, 20, 22, 25, 28, 30, 31, 32, 34,
+];
+
+/// U and D turns, half turns of the other faces and wide half turns, which
+/// also keep the wings of the middle layer edges in it and the corners
+/// oriented.
+pub(crate) const PHASE4_MOVES: [usize; 16] =
+    [0, 1, 2, 4, 7, 10, 13, 16, 18, 19, 20, 22, 25, 28, 31, 34];
+
+const UD_SLOTS: usize = 8;
+const PAIRINGS: usize = 40320 * 24 * 2;
+
+pub(crate) struct Tables {
+    pub centre_moves: Vec<MaskPermutation>,
+    pub wing_moves: Vec<MaskPermutation>,
+    /// The x and z rotations of the centres, see `Geometry::centre_rotations`.
+    pub centre_rotations: Vec<MaskPermutation>,
+    /// The wing positions in one class, whichever a move takes the others
+    /// to unless it is a wide quarter turn or an R or L quarter turn.
+    pub class_mask: u32,
+    /// The wing positions in that class, one on each 3x3 edge, by edge.
+    pub class_positions: [u8; EDGES],
+    /// The wing positions in the other class, by edge.
+    pub other_positions: [u8; EDGES],
+    /// Whether each move is an odd permutation of the wings.
+    pub wing_parity_flips: [bool; MOVES],
+    /// Phase 1 distance to the U and D centres being on U and D and the
+    /// wings being in an even permutation, by `2 * subset_rank` of the
+    /// positions holding them plus the parity. Every move has a counterpart
+    /// under a rotation, so after one the table also gives the distance for
+    /// the F and B or L and R centres.
+    pub centre_axis_distance: Vec<u8>,
+    /// Phase 2 distances to the wings from `class_positions` being back in
+    /// them, and to centres phase 3 can take to ones phase 4 can solve, by
+    /// the `subset_rank` of the positions holding the U, R and F centres.
+    pub wing_class_distance: Vec<u8>,
+    pub centre_orbit_distance: Vec<u8>,
+    /// The corner twist, as on a 3x3, after each move.
+    pub twist_moves: Vec<[u16; MOVES]>,
+    /// The positions of the middle layer edges' wings in each class, as a
+    /// 3x3 slice coordinate over that class's wing positions, after each
+    /// phase 3 move.
+    pub slice_moves: [Vec<[u16; PHASE3_MOVES.len()]>; 2],
+    /// Phase 3 distances to the wings of the middle layer edges being back
+    /// in it, by `SLICES *` one class's slice coordinate plus the other's,
+    /// to that and oriented corners for each class, by `SLICES * twist`
+    /// plus its slice coordinate, and to centres phase 4 can solve.
+    pub slice_distance: Vec<u8>,
+    pub twist_slice_distance: [Vec<u8>; 2],
+    pub centre_coset_distance: Vec<u8>,
+    /// The masks of U, R and F centre positions phase 4 can solve, in
+    /// order, and the index of the one each phase 4 move takes them to.
+    pub centre_states: Vec<u32>,
+    pub centre_state_moves: Vec<[u16; PHASE4_MOVES.len()]>,
+    /// Phase 4 distance to solved centres, by index in `centre_states`.
+    pub centre_distance: Vec<u8>,
+    /// Phase 4 distance to paired edges whose permutation has the parity of
+    /// the corners', see `Tables::pairing`.
+    pub pairing_distance: Vec<u8>,
+    pub pairing_moves: Vec<[u16; PHASE4_MOVES.len()]>,
+    pub slice_pairing_moves: [[u8; PHASE4_MOVES.len()]; 24],
+    pub parity_flips: [bool; PHASE4_MOVES.len()],
+    /// Phase 4 distance to solved centres with the middle layer edges
+    /// paired and the parity right, by `2 * (24 * centre state + the
+    /// middle layer part of the pairing)` plus the parity.
+    pub centre_slice_distance: Vec<u8>,
+}
+
+pub(crate) fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+/// Bits of the positions `p` with `in_mask(p)`.
+pub(crate) fn mask(in_mask: impl Fn(usize) -> bool) -> u32 {
+    (0..24)
+        .filter(|&p| in_mask(p))
+        .fold(0, |mask, p| mask | 1 << p)
+}
+
+/// Breadth-first distances from `goals` over the states `0..size`, where
+/// `next(state, i)` is the state after move `i`. Once fewer states are left
+/// than the last layer reached, the rest are found by looking for a move to
+/// that layer instead, which is cheaper as every move's inverse is a move.
+fn distance(
+    size: usize,
+    goals: impl IntoIterator<Item = usize>,
+    moves: usize,
+    next: impl Fn(usize, usize) -> usize,
+) -> Vec<u8> {
+    let mut distance = vec![u8::MAX; size];
+    let mut frontier = Vec::new();
+    for goal in goals {
+        distance[goal] = 0;
+        frontier.push(goal);
+    }
+    let mut left = size - frontier.len();
+    let mut depth = 0;
+
+    while !frontier.is_empty() && frontier.len() <= left {
+        depth += 1;
+        let mut next_frontier = Vec::new();
+        for state in frontier {
+            for i in 0..moves {
+                let moved = next(state, i);
+                if distance[moved] == u8::MAX {
+                    distance[moved] = depth;
+                    next_frontier.push(moved);
+                }
+            }
+        }
+        left -= next_frontier.len();
+        frontier = next_frontier;
+    }
+
+    let mut reached = frontier.len();
+    while reached > 0 && left > 0 {
+        depth += 1;
+        reached = 0;
+        for state in 0..size {
+            if distance[state] == u8::MAX
+                && (0..moves).any(|i| distance[next(state, i)] == depth - 1)
+            {
+                distance[state] = depth;
+                reached += 1;
+            }
+        }
+        left -= reached;
+    }
+    distance
+}
+
+/// Distances from `goals` for masks turned by `moves`, by `subset_rank`.
+fn mask_distance(goals: impl IntoIterator<Item = u32>, moves: &[&MaskPermutation]) -> Vec<u8> {
+    let mut goals = goals.into_iter().peekable();
+    let masks = masks(goals.peek().expect("there is a goal").count_ones() as usize);
+    distance(
+        masks.len(),
+        goals.map(subset_rank),
+        moves.len(),
+        |rank, i| subset_rank(moves[i].apply(masks[rank])),
+    )
+}
+
+impl Tables {
+    /// The state of the pairing of the edges of a cube in the phase 4
+    /// subgroup. Each 3x3 edge has a wing position in each class, and each
+    /// wing in `class_positions` has a partner in `other_positions`, the
+    /// other wing of its own edge. The edges are paired when every partner
+    /// is on the same edge, i.e. when the permutation taking each edge to
+    /// the one holding the partner of its wing is the identity. Phase 4
+    /// moves keep the middle layer edges apart, so that permutation is
+    /// counted separately there. Each move changes it the same way however
+    /// the wings are labelled.
+    ///
+    /// Once paired, the edges are only solvable as a 3x3 if their
+    /// permutation has the same parity as the corners', which is the last
+    /// part of the coordinate.
+    pub fn pairing(&self, cube: &FourCube) -> usize {
+        let home = |p: u8| geometry().wing_edges[cube.wings[p as usize] as usize];
+        let edges = self.class_positions.map(home);
+        let mut holding = [0; EDGES];
+        for (edge, &p) in self.other_positions.iter().enumerate() {
+            holding[home(p) as usize] = edge as u8;
+        }
+        let partners = edges.map(|edge| holding[edge as usize]);
+        let slice = partners[UD_SLOTS..].iter().map(|edge| edge - 8);
+        let parity = permutation_parity(&edges) != permutation_parity(&cube.corners.cp);
+
+        (permutation_rank(&partners[..UD_SLOTS]) * 24
+            + permutation_rank(&slice.collect::<Vec<_>>()))
+            * 2
+            + parity as usize
+    }
+
+    /// The pairing after phase 4 move `i`.
+    pub fn pairing_move(&self, pairing: usize, i: usize) -> usize {
+        let (rest, parity) = (pairing / 2, pairing % 2);
+        let (edges, slice) = (rest / 24, rest % 24);
+        (self.pairing_moves[edges][i] as usize * 24 + self.slice_pairing_moves[slice][i] as usize)
+            * 2
+            + (parity ^ self.parity_flips[i] as usize)
+    }
+}
+
+/// Every arrangement of the centres a phase can solve, by the positions of
+/// the U, R and F centres, in order.
+fn solvable(distance: &[u8]) -> impl Iterator<Item = u32> {
+    masks(CENTRES / 2)
+        .into_iter()
+        .zip(distance)
+        .filter(|&(_, &distance)| distance != u8::MAX)
+        .map(|(mask, _)| mask)
+}
+
+/// The permutations of the moves in a phase.
+fn phase<'a>(permutations: &'a [MaskPermutation], moves: &[usize]) -> Vec<&'a MaskPermutation> {
+    moves.iter().map(|&m| &permutations[m]).collect()
+}
+
+fn build_tables() -> Tables {
+    let geometry = geometry();
+    let centre_moves: Vec<_> = geometry
+        .centre_moves
+        .iter()
+        .map(MaskPermutation::new)
+        .collect();
+    let wing_moves: Vec<_> = geometry
+        .wing_moves
+        .iter()
+        .map(MaskPermutation::new)
+        .collect();
+
+    // The positions phase 3 moves can take the wing at position 0 to form
+    // one class.
+    let mut class = 1u32;
+    loop {
+        let grown = PHASE3_MOVES
+            .iter()
+            .fold(class, |class, &m| class | wing_moves[m].apply(class));
+        if grown == class {
+            break;
+        }
+        class = grown;
+    }
+    assert_eq!(class.count_ones() as usize, EDGES);
+    let mut class_positions = [0; EDGES];
+    let mut other_positions = [0; EDGES];
+    for p in 0..WINGS {
+        let edge = geometry.wing_edges[p] as usize;
+        if class >> p & 1 == 1 {
+            class_positions[edge] = p as u8;
+        } else {
+            other_positions[edge] = p as u8;
+        }
+    }
+    // Where a move takes the wings of each class, by edge.
+    let edge_move = |m: usize| {
+        let edge = |p: u8| geometry.wing_edges[geometry.wing_moves[m][p as usize] as usize];
+        [class_positions.map(edge), other_positions.map(edge)]
+    };
+
+    let wing_parity_flips = geometry
+        .wing_moves
+        .map(|targets| permutation_parity(&targets));
+    let centre_axis_moves = phase(&centre_moves, &PHASE1_MOVES);
+    let axis_masks = masks(8);
+    let centre_axis_distance = distance(
+        axis_masks.len() * 2,
+        [2 * subset_rank(mask(|c| c / 4 % 3 == 0))],
+        MOVES,
+        |index, m| {
+            2 * subset_rank(centre_axis_moves[m].apply(axis_masks[index / 2]))
+                + ((index % 2) ^ wing_parity_flips[m] as usize)
+        },
+    );
+
+    let twist_moves = (0..TWISTS)
+        .map(|twist| {
+            let mut corners = CubieCube::SOLVED;
+            corners.set_twist(twist);
+            std::array::from_fn(|m| corners.turn(m / 6, m % 3 + 1).twist() as u16)
+        })
+        .collect::<Vec<_>>();
+    let phase3_edge_moves = PHASE3_MOVES.map(edge_move);
+    let slice_moves = [0, 1].map(|class| {
+        (0..SLICES)
+            .map(|slice| {
+                let mut edges = CubieCube::SOLVED;
+                edges.set_slice(slice);
+                phase3_edge_moves.map(|moves| {
+                    let mut moved = edges;
+                    for (edge, &target) in moves[class].iter().enumerate() {
+                        moved.ep[target as usize] = edges.ep[edge];
+                    }
+                    moved.slice() as u16
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    let slice_distance = distance(SLICES * SLICES, [0], PHASE3_MOVES.len(), |slices, i| {
+        slice_moves[0][slices / SLICES][i] as usize * SLICES
+            + slice_moves[1][slices % SLICES][i] as usize
+    });
+    let twist_slice_distance = [0, 1].map(|class| {
+        distance(TWISTS * SLICES, [0], PHASE3_MOVES.len(), |index, i| {
+            twist_moves[index / SLICES][PHASE3_MOVES[i]] as usize * SLICES
+                + slice_moves[class][index % SLICES][i] as usize
+        })
+    });
+
+    let phase4_edge_moves = PHASE4_MOVES.map(edge_move);
+    let partner_move = |partners: &[u8], offset: usize, [class, other]: &[[u8; EDGES]; 2]| {
+        let mut moved = vec![0; partners.len()];
+        for (edge, &partner) in partners.iter().enumerate() {
+            moved[class[edge + offset] as usize - offset] =
+                other[partner as usize + offset] - offset as u8;
+        }
+        permutation_rank(&moved)
+    };
+    let pairing_moves = (0..40320)
+        .map(|rank| {
+            let partners = permutation_from_rank(rank, UD_SLOTS);
+            phase4_edge_moves.map(|moves| partner_move(&partners, 0, &moves) as u16)
+        })
+        .collect();
+    let slice_pairing_moves = std::array::from_fn(|rank| {
+        let partners = permutation_from_rank(rank, EDGES - UD_SLOTS);
+        phase4_edge_moves.map(|moves| partner_move(&partners, UD_SLOTS, &moves) as u8)
+    });
+    let parity_flips = std::array::from_fn(|i| {
+        let m = PHASE4_MOVES[i];
+        let corners = CubieCube::SOLVED.turn(m / 6, m % 3 + 1);
+        permutation_parity(&phase4_edge_moves[i][0]) != permutation_parity(&corners.cp)
+    });
+
+    let solved_centres = mask(|c| c < CENTRES / 2);
+    let centre_mask_distance =
+        mask_distance([solved_centres], &phase(&centre_moves, &PHASE4_MOVES));
+    let centre_states: Vec<u32> = solvable(&centre_mask_distance).collect();
+    let centre_state_moves = centre_states
+        .iter()
+        .map(|&centres| {
+            PHASE4_MOVES.map(|m| {
+                centre_states
+                    .binary_search(&centre_moves[m].apply(centres))
+                    .expect("phase 4 moves keep the centres solvable") as u16
+            })
+        })
+        .collect();
+    let centre_distance = centre_states
+        .iter()
+        .map(|&centres| centre_mask_distance[subset_rank(centres)])
+        .collect();
+    let centre_coset_distance = mask_distance(
+        centre_states.iter().copied(),
+        &phase(&centre_moves, &PHASE3_MOVES),
+    );
+    let centre_orbit_distance = mask_distance(
+        solvable(&centre_coset_distance),
+        &phase(&centre_moves, &PHASE2_MOVES),
+    );
+
+    let mut tables = Tables {
+        wing_parity_flips,
+        centre_axis_distance,
+        wing_class_distance: mask_distance([class], &phase(&wing_moves, &PHASE2_MOVES)),
+        centre_orbit_distance,
+        twist_moves,
+        slice_moves,
+        slice_distance,
+        twist_slice_distance,
+        centre_coset_distance,
+        centre_states,
+        centre_state_moves,
+        centre_distance,
+        centre_rotations: geometry
+            .centre_rotations
+            .iter()
+            .map(MaskPermutation::new)
+            .collect(),
+        centre_moves,
+        wing_moves,
+        class_mask: class,
+        class_positions,
+        other_positions,
+        pairing_distance: Vec::new(),
+        pairing_moves,
+        slice_pairing_moves,
+        parity_flips,
+        centre_slice_distance: Vec::new(),
+    };
+    tables.pairing_distance = pairing_distance(&tables);
+    tables.centre_slice_distance = centre_slice_distance(&tables, solved_centres);
+    tables
+}
+
+fn pairing_distance(tables: &Tables) -> Vec<u8> {
+    distance(PAIRINGS, [0], PHASE4_MOVES.len(), |pairing, i| {
+        tables.pairing_move(pairing, i)
+    })
+}
+
+fn centre_slice_distance(tables: &Tables, solved_centres: u32) -> Vec<u8> {
+    let solved = tables
+        .centre_states
+        .binary_search(&solved_centres)
+        .expect("solved centres are solvable");
+    distance(
+        tables.centre_states.len() * 48,
+        [solved * 48],
+        PHASE4_MOVES.len(),
+        |index, i| {
+            let (rest, parity) = (index / 2, index % 2);
+            let (centres, slice) = (rest / 24, rest % 24);
+            (tables.centre_state_moves[centres][i] as usize * 24
+                + tables.slice_pairing_moves[slice][i] as usize)
+                * 2
+                + (parity ^ tables.parity_flips[i] as usize)
+        },
+    )
+}
@@ -0,0 +1,4 @@
+pub mod cross;
+pub mod four;
+pub mod three;
+pub mod two;
@@ -0,0 +1,151 @@
+use super::cubie::{CORNERS, CubieCube, EDGES};
+
+pub(crate) const TWISTS: usize = 2187;
+pub(crate) const FLIPS: usize = 2048;
+pub(crate) const SLICES: usize = 495;
+pub(crate) const CORNER_PERMUTATIONS: usize = 40320;
+pub(crate) const UD_EDGE_PERMUTATIONS: usize = 40320;
+pub(crate) const SLICE_PERMUTATIONS: usize = 24;
+
+const FIRST_SLICE_EDGE: u8 = 8;
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+fn rank_permutation(permutation: &[u8]) -> usize {
+    let mut rank = 0;
+    for i in 0..permutation.len() {
+        let smaller = permutation[i + 1..]
+            .iter()
+            .filter(|&&p| p < permutation[i])
+            .count();
+        rank = rank * (permutation.len() - i) + smaller;
+    }
+    rank
+}
+
+fn unrank_permutation(mut rank: usize, permutation: &mut [u8]) {
+    let n = permutation.len();
+    let mut digits = vec![0; n];
+    for i in (0..n).rev() {
+        digits[i] = rank % (n - i);
+        rank /= n - i;
+    }
+    let mut remaining: Vec<u8> = (0..n as u8).collect();
+    for i in 0..n {
+        permutation[i] = remaining.remove(digits[i]);
+    }
+}
+
+impl CubieCube {
+    /// Orientation of the first seven corners in base 3; the last is implied.
+    pub fn twist(&self) -> usize {
+        self.co[..CORNERS - 1]
+            .iter()
+            .fold(0, |acc, &o| acc * 3 + o as usize)
+    }
+
+    pub fn set_twist(&mut self, mut twist: usize) {
+        let mut sum = 0;
+        for i in (0..CORNERS - 1).rev() {
+            self.co[i] = (twist % 3) as u8;
+            sum += self.co[i];
+            twist /= 3;
+        }
+        self.co[CORNERS - 1] = (3 - sum % 3) % 3;
+    }
+
+    /// Orientation of the first eleven edges in base 2; the last is implied.
+    pub fn flip(&self) -> usize {
+        self.eo[..EDGES - 1]
+            .iter()
+            .fold(0, |acc, &o| acc * 2 + o as usize)
+    }
+
+    pub fn set_flip(&mut self, mut flip: usize) {
+        let mut sum = 0;
+        for i in (0..EDGES - 1).rev() {
+            self.eo[i] = (flip % 2) as u8;
+            sum += self.eo[i];
+            flip /= 2;
+        }
+        self.eo[EDGES - 1] = sum % 2;
+    }
+
+    /// Which four positions hold the FR, FL, BL and BR edges, ignoring their
+    /// order. Zero when they are all in the middle layer.
+    pub fn slice(&self) -> usize {
+        let mut positions: Vec<usize> = (0..EDGES)
+            .filter(|&i| self.ep[i] >= FIRST_SLICE_EDGE)
+            .map(|i| EDGES - 1 - i)
+            .collect();
+        positions.sort_unstable();
+        positions
+            .iter()
+            .enumerate()
+            .map(|(k, &p)| binomial(p, k + 1))
+            .sum()
+    }
+
+    pub fn set_slice(&mut self, mut slice: usize) {
+        let mut occupied = [false; EDGES];
+        for k in (1..=4).rev() {
+            let mut c = k - 1;
+            while binomial(c + 1, k) <= slice {
+                c += 1;
+            }
+            slice -= binomial(c, k);
+            occupied[EDGES - 1 - c] = true;
+        }
+
+        let (mut slice_edge, mut other_edge) = (FIRST_SLICE_EDGE, 0);
+        for (edge, &occupied) in self.ep.iter_mut().zip(&occupied) {
+            if occupied {
+                *edge = slice_edge;
+                slice_edge += 1;
+            } else {
+                *edge = other_edge;
+                other_edge += 1;
+            }
+        }
+    }
+
+    pub fn corner_permutation(&self) -> usize {
+        rank_permutation(&self.cp)
+    }
+
+    pub fn set_corner_permutation(&mut self, permutation: usize) {
+        unrank_permutation(permutation, &mut self.cp);
+    }
+
+    /// Permutation of the eight U and D layer edges. Only meaningful once the
+    /// middle layer edges are back in the middle layer.
+    pub fn ud_edge_permutation(&self) -> usize {
+        rank_permutation(&self.ep[..FIRST_SLICE_EDGE as usize])
+    }
+
+    pub fn set_ud_edge_permutation(&mut self, permutation: usize) {
+        unrank_permutation(permutation, &mut self.ep[..FIRST_SLICE_EDGE as usize]);
+    }
+
+    /// Permutation of the four middle layer edges within the middle layer.
+    pub fn slice_permutation(&self) -> usize {
+        let slice: Vec<u8> = self.ep[FIRST_SLICE_EDGE as usize..]
+            .iter()
+            .map(|&e| e - FIRST_SLICE_EDGE)
+            .collect();
+        rank_permutation(&slice)
+    }
+
+    pub fn set_slice_permutation(&mut self, permutation: usize) {
+        let slice = &mut self.ep[FIRST_SLICE_EDGE as usize..];
+        unrank_permutation(permutation, slice);
+        for edge in slice {
+            *edge += FIRST_SLICE_EDGE;
+        }
+    }
+}
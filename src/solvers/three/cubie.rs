@@ -0,0 +1,121 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+// Corners: URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB.
+// Edges: UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR.
+pub(crate) const CORNERS: usize = 8;
+pub(crate) const EDGES: usize = 12;
+
+/// Cubie-level description of a 3x3: position i holds piece `cp[i]` / `ep[i]`
+/// with the given orientation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct CubieCube {
+    pub cp: [u8; CORNERS],
+    pub co: [u8; CORNERS],
+    pub ep: [u8; EDGES],
+    pub eo: [u8; EDGES],
+}
+
+// Clockwise quarter turns of U, R, F, D, L and B.
+pub(crate) const FACE_TURNS: [CubieCube; 6] = [
+    CubieCube {
+        cp: [3, 0, 1, 2, 4, 5, 6, 7],
+        co: [0, 0, 0, 0, 0, 0, 0, 0],
+        ep: [3, 0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [4, 1, 2, 0, 7, 5, 6, 3],
+        co: [2, 0, 0, 1, 1, 0, 0, 2],
+        ep: [8, 1, 2, 3, 11, 5, 6, 7, 4, 9, 10, 0],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [1, 5, 2, 3, 0, 4, 6, 7],
+        co: [1, 2, 0, 0, 2, 1, 0, 0],
+        ep: [0, 9, 2, 3, 4, 8, 6, 7, 1, 5, 10, 11],
+        eo: [0, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 5, 6, 7, 4],
+        co: [0, 0, 0, 0, 0, 0, 0, 0],
+        ep: [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 2, 6, 3, 4, 1, 5, 7],
+        co: [0, 1, 2, 0, 0, 2, 1, 0],
+        ep: [0, 1, 10, 3, 4, 5, 9, 7, 8, 2, 6, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 3, 7, 4, 5, 2, 6],
+        co: [0, 0, 1, 2, 0, 0, 2, 1],
+        ep: [0, 1, 2, 11, 4, 5, 6, 10, 8, 9, 3, 7],
+        eo: [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    },
+];
+
+impl CubieCube {
+    pub const SOLVED: CubieCube = CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; CORNERS],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; EDGES],
+    };
+
+    /// Uniformly random cube among the 43,252,003,274,489,856,000 reachable ones.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> CubieCube {
+        let mut cube = CubieCube::SOLVED;
+        cube.cp.shuffle(rng);
+        cube.ep.shuffle(rng);
+        if permutation_parity(&cube.cp) != permutation_parity(&cube.ep) {
+            cube.ep.swap(0, 1);
+        }
+
+        for i in 0..CORNERS - 1 {
+            cube.co[i] = rng.random_range(0..3);
+        }
+        cube.co[CORNERS - 1] = (3 - cube.co[..CORNERS - 1].iter().sum::<u8>() % 3) % 3;
+        for i in 0..EDGES - 1 {
+            cube.eo[i] = rng.random_range(0..2);
+        }
+        cube.eo[EDGES - 1] = cube.eo[..EDGES - 1].iter().sum::<u8>() % 2;
+
+        cube
+    }
+
+    /// Applies `other` after `self`.
+    pub fn multiply(&self, other: &CubieCube) -> CubieCube {
+        let mut result = *self;
+        for i in 0..CORNERS {
+            let from = other.cp[i] as usize;
+            result.cp[i] = self.cp[from];
+            result.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+        for i in 0..EDGES {
+            let from = other.ep[i] as usize;
+            result.ep[i] = self.ep[from];
+            result.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+        result
+    }
+
+    /// Applies `turns` clockwise quarter turns of the face at `face` in
+    /// FACE_TURNS.
+    pub fn turn(&self, face: usize, turns: usize) -> CubieCube {
+        (0..turns).fold(*self, |cube, _| cube.multiply(&FACE_TURNS[face]))
+    }
+}
+
+pub(crate) fn permutation_parity(permutation: &[u8]) -> bool {
+    let mut parity = false;
+    for i in 0..permutation.len() {
+        for j in i + 1..permutation.len() {
+            if permutation[i] > permutation[j] {
+                parity = !parity;
+            }
+        }
+    }
+    parity
+}
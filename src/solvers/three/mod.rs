@@ -0,0 +1,136 @@
+pub(crate) mod coordinates;
+pub(crate) mod cubie;
+mod search;
+mod tables;
+
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::solvers::cross;
+use cubie::CubieCube;
+use rand::Rng;
+
+const FACES: [MoveFace; 6] = [
+    MoveFace::Up,
+    MoveFace::Right,
+    MoveFace::Front,
+    MoveFace::Down,
+    MoveFace::Left,
+    MoveFace::Back,
+];
+const TYPES: [MoveType; 3] = [MoveType::Normal, MoveType::Double, MoveType::Prime];
+
+/// Random-state scrambles are accepted once the solver finds a solution of at
+/// most this many moves.
+pub const DEFAULT_MAX_LENGTH: usize = 21;
+
+/// Piece permutation and orientation of a 3x3, in the fixed orientation with
+/// U on top and F in front.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ThreeByThreeState {
+    cube: CubieCube,
+}
+
+impl Default for ThreeByThreeState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl ThreeByThreeState {
+    pub fn solved() -> ThreeByThreeState {
+        ThreeByThreeState {
+            cube: CubieCube::SOLVED,
+        }
+    }
+
+    pub(crate) fn from_cubie(cube: CubieCube) -> ThreeByThreeState {
+        ThreeByThreeState { cube }
+    }
+
+    /// Picks a state uniformly at random from every reachable 3x3 state.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> ThreeByThreeState {
+        ThreeByThreeState {
+            cube: CubieCube::random(rng),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.cube == CubieCube::SOLVED
+    }
+
+    /// Applies an outer layer move. Wide moves would turn the centres and are
+    /// rejected.
+    pub fn apply_move(&mut self, move_: &Move) {
+        assert_eq!(
+            move_.move_width,
+            MoveWidth::Single,
+            "3x3 state only supports single-layer moves"
+        );
+        let face = face_index(move_.move_face);
+        let turns = TYPES
+            .iter()
+            .position(|move_type| *move_type == move_.move_type)
+            .expect("every move type is in TYPES")
+            + 1;
+        self.cube = self.cube.turn(face, turns);
+    }
+
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for move_ in moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Solves the state with the two-phase algorithm, returning the first
+    /// solution found with at most `max_length` moves.
+    pub fn solve(&self, max_length: usize) -> Option<Vec<Move>> {
+        self.solve_avoiding(max_length, None, None)
+    }
+
+    /// Like `solve`, but the first move is not on the same axis as `first`
+    /// and the last move is not on the same axis as `last`, so that the
+    /// solution can sit between fixed moves without cancelling with them.
+    pub fn solve_avoiding(
+        &self,
+        max_length: usize,
+        first: Option<MoveFace>,
+        last: Option<MoveFace>,
+    ) -> Option<Vec<Move>> {
+        let axis = |face: Option<MoveFace>| face.map(|face| face_index(face) % 3);
+        let ends = search::Ends {
+            first: axis(first),
+            last: axis(last),
+        };
+        search::solve(&self.cube, max_length, ends)
+            .map(|solution| solution.into_iter().map(to_move).collect())
+    }
+
+    /// Fewest moves to solve the cross on `face`: the four edges around its
+    /// centre in place and oriented.
+    pub fn cross_length(&self, face: MoveFace) -> usize {
+        cross::distance(&self.cube, face_index(face))
+    }
+
+    /// An optimal solution to the cross on `face`, `cross_length` moves long.
+    pub fn solve_cross(&self, face: MoveFace) -> Vec<Move> {
+        cross::solve(&self.cube, face_index(face))
+            .into_iter()
+            .map(to_move)
+            .collect()
+    }
+}
+
+fn face_index(face: MoveFace) -> usize {
+    FACES
+        .iter()
+        .position(|f| *f == face)
+        .expect("every face is in FACES")
+}
+
+/// The move indexed `face * 3 + quarter turns - 1`.
+fn to_move(m: usize) -> Move {
+    Move {
+        move_face: FACES[m / 3],
+        move_type: TYPES[m % 3],
+        move_width: MoveWidth::Single,
+    }
+}
@@ -0,0 +1,158 @@
+use super::coordinates::{SLICE_PERMUTATIONS, SLICES};
+use super::cubie::CubieCube;
+use super::tables::{MOVES, PHASE2_MOVES, Tables, tables};
+
+const NO_FACE: usize = usize::MAX;
+
+/// Kociemba's two-phase algorithm: phase 1 reaches the subgroup
+/// <U, D, R2, L2, F2, B2> (all orientations solved, middle layer edges in the
+/// middle layer), phase 2 solves the cube within it.
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    max_length: usize,
+    ends: Ends,
+    solution: Vec<usize>,
+}
+
+/// Axes the first and last moves of a solution must avoid, as `face % 3`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ends {
+    pub first: Option<usize>,
+    pub last: Option<usize>,
+}
+
+/// Moves are indexed as `face * 3 + quarter_turns - 1` with faces ordered
+/// U, R, F, D, L, B.
+pub(crate) fn solve(cube: &CubieCube, max_length: usize, ends: Ends) -> Option<Vec<usize>> {
+    let mut search = Search {
+        tables: tables(),
+        cube: *cube,
+        max_length,
+        ends,
+        solution: Vec::new(),
+    };
+    let (twist, flip, slice) = (cube.twist(), cube.flip(), cube.slice());
+
+    for depth in 0..=max_length {
+        if search.phase1(twist, flip, slice, depth) {
+            return Some(search.solution);
+        }
+    }
+    None
+}
+
+fn redundant(previous_face: usize, face: usize) -> bool {
+    // Opposite faces commute, so only one of their orders is searched.
+    previous_face != NO_FACE && (face == previous_face || face + 3 == previous_face)
+}
+
+impl Search<'_> {
+    fn last_face(&self) -> usize {
+        self.solution.last().map_or(NO_FACE, |m| m / 3)
+    }
+
+    /// Whether `m` may come next, given the axis the first move avoids.
+    fn allowed_next(&self, m: usize) -> bool {
+        !self.solution.is_empty() || self.ends.first != Some(m / 3 % 3)
+    }
+
+    /// Whether the solution so far may end here, given the axis the last
+    /// move avoids.
+    fn allowed_end(&self) -> bool {
+        self.solution
+            .last()
+            .is_none_or(|&m| self.ends.last != Some(m / 3 % 3))
+    }
+
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, depth: usize) -> bool {
+        if depth == 0 {
+            // A phase 1 solution ending in a phase 2 move was already tried at
+            // a shorter depth.
+            let ends_in_phase2 = self
+                .solution
+                .last()
+                .is_some_and(|m| PHASE2_MOVES.contains(m));
+            return twist == 0 && flip == 0 && slice == 0 && !ends_in_phase2 && self.start_phase2();
+        }
+
+        let last_face = self.last_face();
+        for m in 0..MOVES {
+            if redundant(last_face, m / 3) || !self.allowed_next(m) {
+                continue;
+            }
+            let twist = self.tables.twist_moves[twist][m] as usize;
+            let flip = self.tables.flip_moves[flip][m] as usize;
+            let slice = self.tables.slice_moves[slice][m] as usize;
+            let bound = self.phase1_bound(twist, flip, slice);
+            if bound >= depth {
+                continue;
+            }
+
+            self.solution.push(m);
+            if self.phase1(twist, flip, slice, depth - 1) {
+                return true;
+            }
+            self.solution.pop();
+        }
+        false
+    }
+
+    fn phase1_bound(&self, twist: usize, flip: usize, slice: usize) -> usize {
+        let twist_slice = self.tables.twist_slice_distance[twist * SLICES + slice];
+        let flip_slice = self.tables.flip_slice_distance[flip * SLICES + slice];
+        twist_slice.max(flip_slice) as usize
+    }
+
+    fn start_phase2(&mut self) -> bool {
+        let cube = self
+            .solution
+            .iter()
+            .fold(self.cube, |cube, &m| cube.turn(m / 3, m % 3 + 1));
+        let corners = cube.corner_permutation();
+        let edges = cube.ud_edge_permutation();
+        let slice = cube.slice_permutation();
+
+        let remaining = self.max_length - self.solution.len();
+        let phase1_length = self.solution.len();
+        for depth in self.phase2_bound(corners, edges, slice)..=remaining {
+            if self.phase2(corners, edges, slice, depth) {
+                return true;
+            }
+            self.solution.truncate(phase1_length);
+        }
+        false
+    }
+
+    fn phase2(&mut self, corners: usize, edges: usize, slice: usize, depth: usize) -> bool {
+        if depth == 0 {
+            return corners == 0 && edges == 0 && slice == 0 && self.allowed_end();
+        }
+
+        let last_face = self.last_face();
+        for (i, &m) in PHASE2_MOVES.iter().enumerate() {
+            if redundant(last_face, m / 3) || !self.allowed_next(m) {
+                continue;
+            }
+            let corners = self.tables.corner_moves[corners][i] as usize;
+            let edges = self.tables.ud_edge_moves[edges][i] as usize;
+            let slice = self.tables.slice_permutation_moves[slice][i] as usize;
+            if self.phase2_bound(corners, edges, slice) >= depth {
+                continue;
+            }
+
+            self.solution.push(m);
+            if self.phase2(corners, edges, slice, depth - 1) {
+                return true;
+            }
+            self.solution.pop();
+        }
+        false
+    }
+
+    fn phase2_bound(&self, corners: usize, edges: usize, slice: usize) -> usize {
+        let corner_slice = self.tables.corner_slice_distance[corners * SLICE_PERMUTATIONS + slice];
+        let edge_slice = self.tables.ud_edge_slice_distance[edges * SLICE_PERMUTATIONS + slice];
+        corner_slice.max(edge_slice) as usize
+    }
+}
@@ -0,0 +1,112 @@
+use super::coordinates::{
+    CORNER_PERMUTATIONS, FLIPS, SLICE_PERMUTATIONS, SLICES, TWISTS, UD_EDGE_PERMUTATIONS,
+};
+use super::cubie::CubieCube;
+use std::sync::OnceLock;
+
+/// Every face turned by one, two or three quarter turns: U, U2, U', R, ...
+pub(crate) const MOVES: usize = 18;
+
+/// The moves that keep a cube inside the phase 2 subgroup <U, D, R2, L2, F2, B2>.
+pub(crate) const PHASE2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+pub(crate) struct Tables {
+    pub twist_moves: Vec<[u16; MOVES]>,
+    pub flip_moves: Vec<[u16; MOVES]>,
+    pub slice_moves: Vec<[u16; MOVES]>,
+    pub corner_moves: Vec<[u16; PHASE2_MOVES.len()]>,
+    pub ud_edge_moves: Vec<[u16; PHASE2_MOVES.len()]>,
+    pub slice_permutation_moves: Vec<[u16; PHASE2_MOVES.len()]>,
+    pub twist_slice_distance: Vec<u8>,
+    pub flip_slice_distance: Vec<u8>,
+    pub corner_slice_distance: Vec<u8>,
+    pub ud_edge_slice_distance: Vec<u8>,
+}
+
+pub(crate) fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+fn move_table<const N: usize>(
+    size: usize,
+    moves: [usize; N],
+    set: fn(&mut CubieCube, usize),
+    get: fn(&CubieCube) -> usize,
+) -> Vec<[u16; N]> {
+    (0..size)
+        .map(|coordinate| {
+            let mut cube = CubieCube::SOLVED;
+            set(&mut cube, coordinate);
+            moves.map(|m| get(&cube.turn(m / 3, m % 3 + 1)) as u16)
+        })
+        .collect()
+}
+
+/// Breadth-first distances to solved over the product of two coordinates,
+/// indexed by `first * second_size + second`.
+fn distance_table<const N: usize>(first_moves: &[[u16; N]], second_moves: &[[u16; N]]) -> Vec<u8> {
+    let second_size = second_moves.len();
+    let mut distance = vec![u8::MAX; first_moves.len() * second_size];
+    distance[0] = 0;
+    let mut frontier = vec![0usize];
+    let mut depth = 0;
+
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for index in frontier {
+            let (first, second) = (index / second_size, index % second_size);
+            for m in 0..N {
+                let neighbour =
+                    first_moves[first][m] as usize * second_size + second_moves[second][m] as usize;
+                if distance[neighbour] == u8::MAX {
+                    distance[neighbour] = depth;
+                    next.push(neighbour);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    distance
+}
+
+fn build_tables() -> Tables {
+    let all_moves: [usize; MOVES] = std::array::from_fn(|m| m);
+
+    let twist_moves = move_table(TWISTS, all_moves, CubieCube::set_twist, CubieCube::twist);
+    let flip_moves = move_table(FLIPS, all_moves, CubieCube::set_flip, CubieCube::flip);
+    let slice_moves = move_table(SLICES, all_moves, CubieCube::set_slice, CubieCube::slice);
+    let corner_moves = move_table(
+        CORNER_PERMUTATIONS,
+        PHASE2_MOVES,
+        CubieCube::set_corner_permutation,
+        CubieCube::corner_permutation,
+    );
+    let ud_edge_moves = move_table(
+        UD_EDGE_PERMUTATIONS,
+        PHASE2_MOVES,
+        CubieCube::set_ud_edge_permutation,
+        CubieCube::ud_edge_permutation,
+    );
+    let slice_permutation_moves = move_table(
+        SLICE_PERMUTATIONS,
+        PHASE2_MOVES,
+        CubieCube::set_slice_permutation,
+        CubieCube::slice_permutation,
+    );
+
+    Tables {
+        twist_slice_distance: distance_table(&twist_moves, &slice_moves),
+        flip_slice_distance: distance_table(&flip_moves, &slice_moves),
+        corner_slice_distance: distance_table(&corner_moves, &slice_permutation_moves),
+        ud_edge_slice_distance: distance_table(&ud_edge_moves, &slice_permutation_moves),
+        twist_moves,
+        flip_moves,
+        slice_moves,
+        corner_moves,
+        ud_edge_moves,
+        slice_permutation_moves,
+    }
+}
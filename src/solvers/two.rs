@@ -0,0 +1,261 @@
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use rand::Rng;
+use std::sync::OnceLock;
+
+// Corners are numbered URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB. Only R, U and F
+// are used, so DBL never moves and every state is reached without rotations.
+const FIXED_CORNER: usize = 6;
+const FREE_POSITIONS: [usize; 7] = [0, 1, 2, 3, 4, 5, 7];
+
+const PERMUTATIONS: usize = 5040;
+const ORIENTATIONS: usize = 729;
+const STATES: usize = PERMUTATIONS * ORIENTATIONS;
+
+const FACES: [MoveFace; 3] = [MoveFace::Right, MoveFace::Up, MoveFace::Front];
+const TYPES: [MoveType; 3] = [MoveType::Normal, MoveType::Double, MoveType::Prime];
+
+// (permutation, orientation) of a clockwise quarter turn of each face in FACES,
+// where position i receives the corner from position cp[i].
+const FACE_TURNS: [([u8; 8], [u8; 8]); 3] = [
+    ([4, 1, 2, 0, 7, 5, 6, 3], [2, 0, 0, 1, 1, 0, 0, 2]),
+    ([3, 0, 1, 2, 4, 5, 6, 7], [0, 0, 0, 0, 0, 0, 0, 0]),
+    ([1, 5, 2, 3, 0, 4, 6, 7], [1, 2, 0, 0, 2, 1, 0, 0]),
+];
+
+pub const DEFAULT_MIN_DEPTH: usize = 4;
+/// Every 2x2 state can be solved in at most this many half turn metric moves.
+pub const MAX_DEPTH: usize = 11;
+
+/// Corner permutation and orientation of a 2x2, with the DBL corner held fixed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TwoByTwoState {
+    cp: [u8; 8],
+    co: [u8; 8],
+}
+
+impl Default for TwoByTwoState {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl TwoByTwoState {
+    pub fn solved() -> TwoByTwoState {
+        TwoByTwoState {
+            cp: [0, 1, 2, 3, 4, 5, 6, 7],
+            co: [0; 8],
+        }
+    }
+
+    /// Picks a state uniformly at random from the 3,674,160 reachable states.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> TwoByTwoState {
+        let permutation = rng.random_range(0..PERMUTATIONS);
+        let orientation = rng.random_range(0..ORIENTATIONS);
+        TwoByTwoState::from_coordinates(permutation, orientation)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == TwoByTwoState::solved()
+    }
+
+    /// Applies an R, U or F move. Other faces would move the fixed DBL corner
+    /// and are rejected.
+    pub fn apply_move(&mut self, move_: &Move) {
+        let face = FACES
+            .iter()
+            .position(|face| *face == move_.move_face)
+            .expect("2x2 state only supports R, U and F moves");
+        assert_eq!(
+            move_.move_width,
+            MoveWidth::Single,
+            "2x2 state only supports single-layer moves"
+        );
+
+        let turns = match move_.move_type {
+            MoveType::Normal => 1,
+            MoveType::Double => 2,
+            MoveType::Prime => 3,
+        };
+        for _ in 0..turns {
+            self.apply_quarter_turn(face);
+        }
+    }
+
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for move_ in moves {
+            self.apply_move(move_);
+        }
+    }
+
+    /// Number of moves in an optimal (half turn metric) solution.
+    pub fn distance(&self) -> usize {
+        let (permutation, orientation) = self.coordinates();
+        tables().distance[permutation * ORIENTATIONS + orientation] as usize
+    }
+
+    /// Returns an optimal solution in the half turn metric.
+    pub fn solve(&self) -> Vec<Move> {
+        let tables = tables();
+        let (mut permutation, mut orientation) = self.coordinates();
+        let mut solution = Vec::new();
+
+        loop {
+            let distance = tables.distance[permutation * ORIENTATIONS + orientation];
+            if distance == 0 {
+                return solution;
+            }
+
+            let (index, next_permutation, next_orientation) = (0..FACES.len() * TYPES.len())
+                .map(|index| {
+                    (
+                        index,
+                        tables.permutation_moves[permutation][index] as usize,
+                        tables.orientation_moves[orientation][index] as usize,
+                    )
+                })
+                .find(|&(_, p, o)| tables.distance[p * ORIENTATIONS + o] < distance)
+                .expect("every unsolved state has a move towards solved");
+
+            solution.push(Move {
+                move_face: FACES[index / TYPES.len()],
+                move_type: TYPES[index % TYPES.len()],
+                move_width: MoveWidth::Single,
+            });
+            permutation = next_permutation;
+            orientation = next_orientation;
+        }
+    }
+
+    fn apply_quarter_turn(&mut self, face: usize) {
+        let (cp, co) = FACE_TURNS[face];
+        let previous = *self;
+        for i in 0..8 {
+            let from = cp[i] as usize;
+            self.cp[i] = previous.cp[from];
+            self.co[i] = (previous.co[from] + co[i]) % 3;
+        }
+    }
+
+    fn coordinates(&self) -> (usize, usize) {
+        let corners: Vec<usize> = FREE_POSITIONS
+            .iter()
+            .map(|&position| match self.cp[position] as usize {
+                7 => FIXED_CORNER,
+                corner => corner,
+            })
+            .collect();
+
+        let mut permutation = 0;
+        for i in 0..corners.len() {
+            let smaller = corners[i + 1..].iter().filter(|&&c| c < corners[i]).count();
+            permutation = permutation * (corners.len() - i) + smaller;
+        }
+
+        let orientation = FREE_POSITIONS[..6]
+            .iter()
+            .fold(0, |acc, &position| acc * 3 + self.co[position] as usize);
+
+        (permutation, orientation)
+    }
+
+    fn from_coordinates(mut permutation: usize, mut orientation: usize) -> TwoByTwoState {
+        let mut state = TwoByTwoState::solved();
+
+        let mut digits = [0usize; 7];
+        for i in (0..7).rev() {
+            digits[i] = permutation % (7 - i);
+            permutation /= 7 - i;
+        }
+        let mut remaining: Vec<usize> = (0..7).collect();
+        for (i, &position) in FREE_POSITIONS.iter().enumerate() {
+            let corner = remaining.remove(digits[i]);
+            state.cp[position] = if corner == FIXED_CORNER {
+                7
+            } else {
+                corner as u8
+            };
+        }
+
+        let mut twist_sum = 0;
+        for &position in FREE_POSITIONS[..6].iter().rev() {
+            state.co[position] = (orientation % 3) as u8;
+            twist_sum += orientation % 3;
+            orientation /= 3;
+        }
+        state.co[7] = ((3 - twist_sum % 3) % 3) as u8;
+
+        state
+    }
+}
+
+struct Tables {
+    permutation_moves: Vec<[u16; 9]>,
+    orientation_moves: Vec<[u16; 9]>,
+    distance: Vec<u8>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+fn build_tables() -> Tables {
+    let move_table = |size: usize,
+                      coordinate: fn(&TwoByTwoState) -> usize,
+                      state_of: fn(usize) -> TwoByTwoState| {
+        (0..size)
+            .map(|value| {
+                let mut entry = [0u16; 9];
+                let mut state = state_of(value);
+                for face in 0..FACES.len() {
+                    for turn in 0..TYPES.len() {
+                        state.apply_quarter_turn(face);
+                        entry[face * TYPES.len() + turn] = coordinate(&state) as u16;
+                    }
+                    // A fourth quarter turn restores the state for the next face.
+                    state.apply_quarter_turn(face);
+                }
+                entry
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let permutation_moves = move_table(
+        PERMUTATIONS,
+        |state| state.coordinates().0,
+        |permutation| TwoByTwoState::from_coordinates(permutation, 0),
+    );
+    let orientation_moves = move_table(
+        ORIENTATIONS,
+        |state| state.coordinates().1,
+        |orientation| TwoByTwoState::from_coordinates(0, orientation),
+    );
+
+    let mut distance = vec![u8::MAX; STATES];
+    distance[0] = 0;
+    let mut frontier = vec![0usize];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for state in frontier {
+            let (permutation, orientation) = (state / ORIENTATIONS, state % ORIENTATIONS);
+            for index in 0..FACES.len() * TYPES.len() {
+                let p = permutation_moves[permutation][index] as usize;
+                let o = orientation_moves[orientation][index] as usize;
+                let neighbour = p * ORIENTATIONS + o;
+                if distance[neighbour] == u8::MAX {
+                    distance[neighbour] = depth;
+                    next.push(neighbour);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    Tables {
+        permutation_moves,
+        orientation_moves,
+        distance,
+    }
+}
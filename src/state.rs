@@ -0,0 +1,199 @@
+use crate::cube::Cube;
+use crate::moves::{Move, MoveFace, MoveType};
+use crate::scramble::Scramble;
+
+/// Faces in the order their stickers are stored.
+pub const FACE_ORDER: [MoveFace; 6] = [
+    MoveFace::Up,
+    MoveFace::Right,
+    MoveFace::Front,
+    MoveFace::Down,
+    MoveFace::Left,
+    MoveFace::Back,
+];
+
+pub(crate) type Vector = [i32; 3];
+
+/// Sticker-level state of an NxN cube. Each sticker is identified by the face
+/// it belongs to on a solved cube, and each face is stored row by row as seen
+/// when looking straight at it in the standard unfolded net (U above F, D below
+/// F, then L F R B from left to right).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CubeState {
+    size: usize,
+    stickers: Vec<MoveFace>,
+}
+
+impl CubeState {
+    pub fn new(cube: Cube) -> CubeState {
+        let size = cube.layers();
+        let stickers = FACE_ORDER
+            .iter()
+            .flat_map(|&face| std::iter::repeat_n(face, size * size))
+            .collect();
+        CubeState { size, stickers }
+    }
+
+    pub fn from_scramble(cube: Cube, scramble: &Scramble) -> CubeState {
+        let mut state = CubeState::new(cube);
+        state.apply_scramble(scramble);
+        state
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn sticker(&self, face: MoveFace, row: usize, col: usize) -> MoveFace {
+        self.face(face)[row * self.size + col]
+    }
+
+    /// All stickers of `face`, row by row.
+    pub fn face(&self, face: MoveFace) -> &[MoveFace] {
+        let area = self.size * self.size;
+        let start = face_index(face) * area;
+        &self.stickers[start..start + area]
+    }
+
+    /// True when every face shows a single colour, in any orientation.
+    pub fn is_solved(&self) -> bool {
+        FACE_ORDER.iter().all(|&face| {
+            let stickers = self.face(face);
+            stickers.iter().all(|&sticker| sticker == stickers[0])
+        })
+    }
+
+    pub fn apply_scramble(&mut self, scramble: &Scramble) {
+        self.apply_moves(&scramble.moves);
+    }
+
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for move_ in moves {
+            self.apply_move(move_);
+        }
+    }
+
+    pub fn apply_move(&mut self, move_: &Move) {
+        let mut stickers = self.stickers.clone();
+        for (index, target) in self.sticker_targets(move_).into_iter().enumerate() {
+            stickers[target] = self.stickers[index];
+        }
+        self.stickers = stickers;
+    }
+
+    /// Where `move_` takes each sticker, as indices into the faces stored in
+    /// FACE_ORDER, row by row.
+    pub(crate) fn sticker_targets(&self, move_: &Move) -> Vec<usize> {
+        let normal = face_normal(move_.move_face);
+        let axis = normal
+            .iter()
+            .position(|&n| n != 0)
+            .expect("normal is a unit vector");
+        let sign = normal[axis];
+
+        let quarter_turns = match move_.move_type {
+            MoveType::Normal => 1,
+            MoveType::Double => 2,
+            MoveType::Prime => 3,
+        };
+        // A clockwise turn seen from the face is a negative rotation about its
+        // outward normal.
+        let rotations = if sign > 0 {
+            3 * quarter_turns % 4
+        } else {
+            quarter_turns
+        };
+
+        let layers = move_.move_width.turned_layers(self.size);
+        let edge = self.size as i32 - 1;
+        let area = self.size * self.size;
+        (0..self.stickers.len())
+            .map(|index| {
+                let (face, mut position) = self.sticker_position(index);
+                if !layers.contains(&(((edge - sign * position[axis]) / 2) as usize)) {
+                    return index;
+                }
+
+                let mut normal = face_normal(face);
+                for _ in 0..rotations {
+                    position = rotate(position, axis);
+                    normal = rotate(normal, axis);
+                }
+                let face = face_with_normal(normal);
+                let (row, col) = self.row_col(face, position);
+                face_index(face) * area + row * self.size + col
+            })
+            .collect()
+    }
+
+    /// The face a sticker is on and the position of its cubie.
+    pub(crate) fn sticker_position(&self, index: usize) -> (MoveFace, Vector) {
+        let area = self.size * self.size;
+        let face = FACE_ORDER[index / area];
+        let (row, col) = ((index % area) / self.size, index % self.size);
+        (face, self.position(face, row, col))
+    }
+
+    /// Position of the cubie holding a sticker, with coordinates running from
+    /// -(size - 1) to size - 1 in steps of two so every cubie centre is integral.
+    fn position(&self, face: MoveFace, row: usize, col: usize) -> Vector {
+        let (origin, right, down) = self.face_frame(face);
+        std::array::from_fn(|i| origin[i] + 2 * (col as i32 * right[i] + row as i32 * down[i]))
+    }
+
+    fn row_col(&self, face: MoveFace, position: Vector) -> (usize, usize) {
+        let (origin, right, down) = self.face_frame(face);
+        let offset: Vector = std::array::from_fn(|i| position[i] - origin[i]);
+        let dot = |v: Vector| (0..3).map(|i| offset[i] * v[i]).sum::<i32>() / 2;
+        (dot(down) as usize, dot(right) as usize)
+    }
+
+    /// Top-left cubie position of a face in the net, and the directions of
+    /// increasing column and row.
+    fn face_frame(&self, face: MoveFace) -> (Vector, Vector, Vector) {
+        let e = self.size as i32 - 1;
+        match face {
+            MoveFace::Up => ([-e, e, -e], [1, 0, 0], [0, 0, 1]),
+            MoveFace::Right => ([e, e, e], [0, 0, -1], [0, -1, 0]),
+            MoveFace::Front => ([-e, e, e], [1, 0, 0], [0, -1, 0]),
+            MoveFace::Down => ([-e, -e, e], [1, 0, 0], [0, 0, -1]),
+            MoveFace::Left => ([-e, e, -e], [0, 0, 1], [0, -1, 0]),
+            MoveFace::Back => ([e, e, -e], [-1, 0, 0], [0, -1, 0]),
+        }
+    }
+}
+
+fn face_index(face: MoveFace) -> usize {
+    FACE_ORDER
+        .iter()
+        .position(|&f| f == face)
+        .expect("every face is in FACE_ORDER")
+}
+
+/// Outward normal with x towards R, y towards U and z towards F.
+fn face_normal(face: MoveFace) -> Vector {
+    match face {
+        MoveFace::Up => [0, 1, 0],
+        MoveFace::Right => [1, 0, 0],
+        MoveFace::Front => [0, 0, 1],
+        MoveFace::Down => [0, -1, 0],
+        MoveFace::Left => [-1, 0, 0],
+        MoveFace::Back => [0, 0, -1],
+    }
+}
+
+fn face_with_normal(normal: Vector) -> MoveFace {
+    FACE_ORDER
+        .into_iter()
+        .find(|&face| face_normal(face) == normal)
+        .expect("rotating a face normal gives another face normal")
+}
+
+/// Positive (right-handed) quarter turn about the given axis.
+fn rotate([x, y, z]: Vector, axis: usize) -> Vector {
+    match axis {
+        0 => [x, -z, y],
+        1 => [z, y, -x],
+        _ => [-y, x, z],
+    }
+}
@@ -0,0 +1,182 @@
+use crate::generators::validator::{can_combine, combine_moves};
+use crate::moves::{Move, MoveFace, MoveType, MoveWidth};
+use crate::scramble::Scramble;
+
+/// A plane through the middle of the cube to mirror moves across, named
+/// after the slice lying in it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MirrorPlane {
+    /// Swaps the left and right faces.
+    M,
+    /// Swaps the top and bottom faces.
+    E,
+    /// Swaps the front and back faces.
+    S,
+}
+
+impl MirrorPlane {
+    fn swaps(&self, face: MoveFace) -> bool {
+        match self {
+            MirrorPlane::M => matches!(face, MoveFace::Left | MoveFace::Right),
+            MirrorPlane::E => matches!(face, MoveFace::Up | MoveFace::Down),
+            MirrorPlane::S => matches!(face, MoveFace::Front | MoveFace::Back),
+        }
+    }
+}
+
+impl Move {
+    /// The move seen in a mirror: faces across the plane swap and every turn
+    /// goes the other way.
+    pub fn mirrored(&self, plane: MirrorPlane) -> Move {
+        let move_face = if plane.swaps(self.move_face) {
+            self.move_face.opposite()
+        } else {
+            self.move_face
+        };
+        Move {
+            move_face,
+            move_type: self.move_type.inverse(),
+            move_width: self.move_width,
+        }
+        .canonical()
+    }
+}
+
+impl Scramble {
+    /// The moves undoing this scramble: reversed, with each turn inverted.
+    pub fn inverse(&self) -> Scramble {
+        Scramble {
+            moves: self.moves.iter().rev().map(Move::inverse).collect(),
+        }
+    }
+
+    /// The scramble with every move mirrored across `plane`. It gives the
+    /// mirror image of this scramble's state.
+    pub fn mirror(&self, plane: MirrorPlane) -> Scramble {
+        Scramble {
+            moves: self
+                .moves
+                .iter()
+                .map(|move_| move_.mirrored(plane))
+                .collect(),
+        }
+    }
+
+    /// The scramble for the cube held after `rotation`, written for the cube
+    /// held as before: the moves turn the same state as `rotation`, this
+    /// scramble and `rotation` undone. For example `z2` turns a scramble for
+    /// white on top into one giving the same state with yellow on top.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rotation` has moves other than `x`, `y` and `z`.
+    pub fn rotate(&self, rotation: &[Move]) -> Scramble {
+        let moves = self
+            .moves
+            .iter()
+            .map(|move_| {
+                // Moves after the first rotation are relabelled by the rest
+                // of it first.
+                let move_face = rotation.iter().rev().fold(move_.move_face, |face, turn| {
+                    assert!(
+                        turn.move_width == MoveWidth::Rotation,
+                        "{turn} is not a rotation"
+                    );
+                    undo_rotation(turn, face)
+                });
+                Move {
+                    move_face,
+                    ..move_.clone()
+                }
+                .canonical()
+            })
+            .collect();
+        Scramble { moves }
+    }
+}
+
+impl Scramble {
+    /// Cancels and merges moves on the same face and width wherever only
+    /// moves on the same axis lie between them, since those commute. For
+    /// example `R L R'` becomes `L` and `Rw R Rw'` becomes `R`. The result
+    /// turns the same state, and other moves keep their order.
+    pub fn simplify(&self) -> Scramble {
+        let mut moves: Vec<Move> = Vec::new();
+        for move_ in &self.moves {
+            let move_ = move_.clone().canonical();
+            let run = moves
+                .iter()
+                .rev()
+                .take_while(|other| {
+                    other.move_face == move_.move_face
+                        || other.move_face.same_axis(&move_.move_face)
+                })
+                .count();
+            let start = moves.len() - run;
+            match moves[start..]
+                .iter()
+                .position(|other| can_combine(other, &move_))
+            {
+                Some(index) => match combine_moves(&moves[start + index], &move_) {
+                    Some(combined) => moves[start + index] = combined,
+                    None => {
+                        moves.remove(start + index);
+                    }
+                },
+                None => moves.push(move_),
+            }
+        }
+        Scramble { moves }
+    }
+}
+
+/// The face that `rotation` turns to where `face` is, i.e. the face that the
+/// inverse rotation turns `face` to.
+fn undo_rotation(rotation: &Move, face: MoveFace) -> MoveFace {
+    // The faces in the order the rotation turns them into each other, seen
+    // from the named face of each axis: R for x, U for y and F for z.
+    let (axis, cycle) = match rotation.move_face {
+        MoveFace::Right | MoveFace::Left => (
+            MoveFace::Right,
+            [
+                MoveFace::Front,
+                MoveFace::Up,
+                MoveFace::Back,
+                MoveFace::Down,
+            ],
+        ),
+        MoveFace::Up | MoveFace::Down => (
+            MoveFace::Up,
+            [
+                MoveFace::Front,
+                MoveFace::Left,
+                MoveFace::Back,
+                MoveFace::Right,
+            ],
+        ),
+        MoveFace::Front | MoveFace::Back => (
+            MoveFace::Front,
+            [
+                MoveFace::Up,
+                MoveFace::Right,
+                MoveFace::Down,
+                MoveFace::Left,
+            ],
+        ),
+    };
+    let quarters = match rotation.move_type {
+        MoveType::Normal => 1,
+        MoveType::Double => 2,
+        MoveType::Prime => 3,
+    };
+    let quarters = if rotation.move_face == axis {
+        quarters
+    } else {
+        4 - quarters
+    };
+
+    match cycle.iter().position(|&other| other == face) {
+        Some(index) => cycle[(index + 4 - quarters) % 4],
+        None => face,
+    }
+}
@@ -0,0 +1,78 @@
+use scramble_gen::solvers::cross::MAX_DEPTH;
+use scramble_gen::{Cube, CubeState, Scramble, ScrambleAnalysis};
+
+fn analyze(moves: &str) -> ScrambleAnalysis {
+    let scramble: Scramble = moves.parse().unwrap();
+    ScrambleAnalysis::from_scramble(&scramble)
+}
+
+#[test]
+fn test_solved_cube_analysis() {
+    let analysis = analyze("");
+    assert_eq!(analysis.cross_lengths, [0; 6]);
+    assert_eq!(analysis.misoriented_edges, [0; 3]);
+    assert_eq!(analysis.f2l_pairs, [4; 6]);
+    assert_eq!(analysis.twisted_corners, 0);
+    assert_eq!(analysis.solved_blocks, 8);
+    assert!(analysis.has_solved_blocks());
+}
+
+#[test]
+fn test_single_turn_analysis() {
+    // Colours in FACE_ORDER: U, R, F, D, L, B.
+    let analysis = analyze("R");
+    assert_eq!(analysis.cross_lengths, [1, 1, 1, 1, 0, 1]);
+    assert_eq!(analysis.misoriented_edges, [0, 4, 0]);
+    assert_eq!(analysis.f2l_pairs, [4, 0, 4, 4, 4, 4]);
+    assert_eq!(analysis.twisted_corners, 4);
+    assert_eq!(analysis.solved_blocks, 4);
+
+    assert_eq!(analyze("F").misoriented_edges, [0, 0, 4]);
+    assert_eq!(analyze("R U").misoriented_edges, [4, 4, 0]);
+}
+
+#[test]
+fn test_sexy_move_analysis() {
+    let analysis = analyze("R U R' U'");
+    assert_eq!(analysis.cross_lengths, [4, 4, 1, 0, 0, 3]);
+    assert_eq!(analysis.f2l_pairs, [1, 1, 2, 4, 3, 3]);
+    assert_eq!(analysis.twisted_corners, 3);
+    assert_eq!(analysis.solved_blocks, 4);
+}
+
+#[test]
+fn test_analysis_follows_the_centres() {
+    // Rotations and wide moves move the centres along with the pieces, so
+    // only what the moves do relative to the centres counts.
+    assert_eq!(analyze("R L' x'"), analyze("R L'"));
+    let wide = analyze("Rw");
+    assert_eq!(wide.cross_lengths, analyze("L").cross_lengths);
+    assert_eq!(wide.solved_blocks, 4);
+}
+
+#[test]
+fn test_random_state_crosses_are_at_most_eight_moves() {
+    for seed in 0..20 {
+        let scramble = Scramble::generate_random_state_seeded(Cube::ThreeByThree, seed);
+        let analysis = ScrambleAnalysis::from_scramble(&scramble);
+        assert!(
+            analysis
+                .cross_lengths
+                .iter()
+                .all(|&length| length <= MAX_DEPTH)
+        );
+        assert!(
+            analysis
+                .misoriented_edges
+                .iter()
+                .all(|&edges| edges % 2 == 0)
+        );
+        assert!(analysis.twisted_corners != 1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "scramble analysis is only for the 3x3")]
+fn test_analysis_rejects_other_cubes() {
+    ScrambleAnalysis::from_state(&CubeState::new(Cube::FourByFour));
+}
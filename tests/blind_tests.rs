@@ -0,0 +1,104 @@
+use scramble_gen::generators::blind::{ORIENTATIONS, orientation};
+use scramble_gen::generators::validator::is_canonical;
+use scramble_gen::{Cube, CubeState, MoveFace, MoveWidth, Scramble};
+use std::collections::HashMap;
+
+#[test]
+fn test_orientations_are_distinct() {
+    for cube in [Cube::ThreeByThree, Cube::FourByFour, Cube::FiveByFive] {
+        let mut states: Vec<CubeState> = Vec::new();
+        for index in 0..ORIENTATIONS {
+            let mut state = CubeState::new(cube);
+            state.apply_moves(&orientation(cube, index));
+            assert!(!states.contains(&state), "{cube} orientation {index}");
+            states.push(state);
+        }
+    }
+}
+
+#[test]
+fn test_orientation_moves_turn_half_the_cube() {
+    for (cube, width) in [
+        (Cube::ThreeByThree, MoveWidth::Wide),
+        (Cube::FourByFour, MoveWidth::Wide),
+        (Cube::FiveByFive, MoveWidth::ThreeWide),
+    ] {
+        for index in 0..ORIENTATIONS {
+            let moves = orientation(cube, index);
+            assert!(moves.len() <= 2);
+            for move_ in &moves {
+                assert_eq!(move_.move_width, width);
+            }
+            if let [first, second] = &moves[..] {
+                assert!([MoveFace::Right, MoveFace::Front].contains(&first.move_face));
+                assert_eq!(second.move_face, MoveFace::Up);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_3x3_blind_is_random_state_with_suffix() {
+    for seed in 0..5 {
+        let scramble = Scramble::generate_blind_seeded(Cube::ThreeByThree, seed);
+        let wide = scramble
+            .moves
+            .iter()
+            .skip_while(|move_| move_.move_width == MoveWidth::Single)
+            .count();
+        assert!(wide <= 2);
+        assert!(scramble.moves.len() - wide <= 21);
+    }
+}
+
+#[test]
+fn test_suffixes_are_uniform() {
+    // 5x5 scrambles never turn three layers before the suffix.
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for seed in 0..(40 * ORIENTATIONS as u64) {
+        let scramble = Scramble::generate_blind_seeded(Cube::FiveByFive, seed);
+        let suffix: Vec<String> = scramble
+            .moves
+            .iter()
+            .filter(|move_| move_.move_width == MoveWidth::ThreeWide)
+            .map(ToString::to_string)
+            .collect();
+        *counts.entry(suffix.join(" ")).or_default() += 1;
+    }
+    assert_eq!(counts.len(), ORIENTATIONS);
+    for (suffix, &count) in &counts {
+        assert!(
+            (15..=70).contains(&count),
+            "{suffix:?} appeared {count} times"
+        );
+    }
+}
+
+#[test]
+fn test_scramble_and_suffix_are_canonical() {
+    for (cube, seeds) in [
+        (Cube::ThreeByThree, 40),
+        (Cube::FourByFour, 200),
+        (Cube::FiveByFive, 200),
+    ] {
+        for seed in 0..seeds {
+            let scramble = Scramble::generate_blind_seeded(cube, seed);
+            assert!(
+                is_canonical(&scramble.moves, cube.layers()),
+                "{cube} seed {seed}: {scramble}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_seeded_blind_scrambles_are_reproducible() {
+    assert_eq!(
+        Scramble::generate_blind_seeded(Cube::FourByFour, 7),
+        Scramble::generate_blind_seeded(Cube::FourByFour, 7)
+    );
+    assert_ne!(
+        Scramble::generate_blind_seeded(Cube::FourByFour, 7),
+        Scramble::generate_blind_seeded(Cube::FourByFour, 8)
+    );
+}
@@ -0,0 +1,149 @@
+use rstest::rstest;
+use scramble_gen::generators::validator::{generate_canonical_scramble_with_faces, is_canonical};
+use scramble_gen::scramble::seeded_rng;
+use scramble_gen::{Cube, Move, MoveFace, MoveType, MoveWidth, Scramble};
+use std::collections::HashMap;
+
+const FACES: [MoveFace; 6] = [
+    MoveFace::Left,
+    MoveFace::Right,
+    MoveFace::Up,
+    MoveFace::Down,
+    MoveFace::Front,
+    MoveFace::Back,
+];
+
+fn moves(input: &str) -> Vec<Move> {
+    input.parse::<Scramble>().unwrap().moves
+}
+
+/// Every sequence of `length` moves over the faces and widths, canonical or
+/// not.
+fn all_sequences(faces: &[MoveFace], widths: &[MoveWidth], length: usize) -> Vec<Vec<Move>> {
+    let mut single = Vec::new();
+    for &move_face in faces {
+        for &move_width in widths {
+            for move_type in [MoveType::Normal, MoveType::Double, MoveType::Prime] {
+                single.push(Move {
+                    move_face,
+                    move_type,
+                    move_width,
+                });
+            }
+        }
+    }
+    let mut sequences = vec![Vec::new()];
+    for _ in 0..length {
+        sequences = sequences
+            .into_iter()
+            .flat_map(|sequence: Vec<Move>| {
+                single.iter().map(move |move_| {
+                    let mut longer = sequence.clone();
+                    longer.push(move_.clone());
+                    longer
+                })
+            })
+            .collect();
+    }
+    sequences
+}
+
+#[rstest]
+#[case("L R Rw", 5, true)]
+#[case("R U R", 3, true)]
+#[case("U D F B", 3, true)]
+#[case("R L", 3, false)]
+#[case("R R", 3, false)]
+#[case("R Rw R", 5, false)]
+#[case("U D U", 3, false)]
+#[case("Rw R", 5, false)]
+#[case("Lw Rw", 5, true)]
+#[case("Lw Rw", 4, false)]
+#[case("L R", 2, false)]
+#[case("R x", 3, false)]
+fn test_is_canonical(#[case] input: &str, #[case] layers: usize, #[case] expected: bool) {
+    assert_eq!(is_canonical(&moves(input), layers), expected);
+}
+
+#[test]
+fn test_generated_scrambles_are_canonical() {
+    for cube in [
+        Cube::TwoByTwo,
+        Cube::ThreeByThree,
+        Cube::FourByFour,
+        Cube::FiveByFive,
+        Cube::SixBySix,
+        Cube::SevenBySeven,
+        Cube::NxN(10),
+    ] {
+        for seed in 0..20 {
+            let scramble = Scramble::generate_seeded(cube, Some(100), seed);
+            assert_eq!(scramble.moves.len(), 100);
+            assert!(
+                is_canonical(&scramble.moves, cube.layers()),
+                "{cube:?}: {scramble}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_longest_scrambles_can_be_generated() {
+    let scramble = Scramble::generate_seeded(Cube::NxN(20), Some(1000), 1);
+    assert_eq!(scramble.moves.len(), 1000);
+    assert!(is_canonical(&scramble.moves, 20));
+}
+
+/// Samples `per_sequence` times as many scrambles as there are canonical
+/// sequences and checks with a chi-squared test that every sequence is
+/// equally likely. The seed is fixed, so the test is deterministic; the
+/// bound is about four standard deviations above the mean of the statistic.
+#[rstest]
+#[case(2, &[MoveFace::Right, MoveFace::Up, MoveFace::Front], &[MoveWidth::Single], 4, 40)]
+#[case(3, &FACES, &[MoveWidth::Single], 3, 30)]
+#[case(5, &FACES, &[MoveWidth::Single, MoveWidth::Wide], 2, 60)]
+fn test_canonical_sequences_are_equally_likely(
+    #[case] layers: usize,
+    #[case] faces: &[MoveFace],
+    #[case] widths: &[MoveWidth],
+    #[case] length: usize,
+    #[case] per_sequence: usize,
+) {
+    let mut observed: HashMap<String, usize> = all_sequences(faces, widths, length)
+        .into_iter()
+        .filter(|sequence| is_canonical(sequence, layers))
+        .map(|sequence| (Scramble { moves: sequence }.to_string(), 0))
+        .collect();
+    let sequences = observed.len();
+
+    let mut rng = seeded_rng(21);
+    for _ in 0..sequences * per_sequence {
+        let moves = generate_canonical_scramble_with_faces(&mut rng, layers, length, faces, widths);
+        let key = Scramble { moves }.to_string();
+        *observed
+            .get_mut(&key)
+            .unwrap_or_else(|| panic!("{key} is not canonical")) += 1;
+    }
+
+    let expected = per_sequence as f64;
+    let chi_squared: f64 = observed
+        .values()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+    let degrees = (sequences - 1) as f64;
+    assert!(
+        chi_squared < degrees + 4.0 * (2.0 * degrees).sqrt(),
+        "chi-squared {chi_squared} over {sequences} sequences"
+    );
+}
+
+#[test]
+fn test_canonical_sequence_counts() {
+    // Length 2 on a 3x3: a block of two moves on one axis (3 axes, 9 ways)
+    // or two single-move blocks on different axes (18 * 12).
+    let count = all_sequences(&FACES, &[MoveWidth::Single], 2)
+        .iter()
+        .filter(|sequence| is_canonical(sequence, 3))
+        .count();
+    assert_eq!(count, 3 * 9 + 18 * 12);
+}
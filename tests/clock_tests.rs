@@ -0,0 +1,101 @@
+use scramble_gen::puzzles::clock::{
+    BACK_PINS, ClockMove, ClockPins, ClockScramble, ClockState, FRONT_PINS,
+};
+
+fn turn(pins: ClockPins, amount: i8) -> ClockMove {
+    ClockMove::Turn { pins, amount }
+}
+
+#[test]
+fn test_move_display() {
+    assert_eq!(turn(ClockPins::UpRight, 3).to_string(), "UR3+");
+    assert_eq!(turn(ClockPins::DownLeft, -2).to_string(), "DL2-");
+    assert_eq!(turn(ClockPins::All, 0).to_string(), "ALL0+");
+    assert_eq!(turn(ClockPins::Up, 6).to_string(), "U6+");
+    assert_eq!(ClockMove::Flip.to_string(), "y2");
+}
+
+#[test]
+fn test_corner_pin_turns_its_quarter() {
+    let mut state = ClockState::solved();
+    state.apply_move(&turn(ClockPins::UpRight, 3));
+    assert_eq!(state.front(), &[0, 3, 3, 0, 3, 3, 0, 0, 0]);
+    // The back of the top right corner is top left seen from behind.
+    assert_eq!(state.back(), &[9, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_flip_turns_the_back() {
+    let mut state = ClockState::solved();
+    state.apply_move(&ClockMove::Flip);
+    assert!(state.is_flipped());
+    assert!(state.is_solved());
+    state.apply_move(&turn(ClockPins::Up, -1));
+    assert_eq!(state.back(), &[11, 11, 11, 11, 11, 11, 0, 0, 0]);
+    assert_eq!(state.front(), &[1, 0, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_back_pins_mirror_the_front() {
+    for pins in BACK_PINS {
+        let mut front_turn = ClockState::solved();
+        front_turn.apply_move(&turn(pins, 4));
+
+        // After the flip, each pin turns the back face as it turned the front
+        // before, and the dials behind it as mirror images.
+        let mut back_turn = ClockState::solved();
+        back_turn.apply_move(&ClockMove::Flip);
+        back_turn.apply_move(&turn(pins, 4));
+        assert_eq!(back_turn.back(), front_turn.front(), "{pins}");
+        assert_eq!(back_turn.front(), front_turn.back(), "{pins}");
+    }
+
+    // The right pins of the back are the left pins of the front.
+    let mut state = ClockState::solved();
+    state.apply_move(&ClockMove::Flip);
+    state.apply_move(&turn(ClockPins::Right, 1));
+    assert_eq!(state.front(), &[11, 0, 0, 0, 0, 0, 11, 0, 0]);
+}
+
+#[test]
+fn test_scramble_follows_wca_order() {
+    for seed in 0..20 {
+        let scramble = ClockScramble::generate_seeded(seed);
+        assert_eq!(scramble.moves.len(), 15);
+        assert_eq!(scramble.moves[9], ClockMove::Flip);
+
+        let pins: Vec<ClockPins> = scramble
+            .moves
+            .iter()
+            .filter_map(|move_| match *move_ {
+                ClockMove::Turn { pins, amount } => {
+                    assert!((-5..=6).contains(&amount));
+                    Some(pins)
+                }
+                ClockMove::Flip => None,
+            })
+            .collect();
+        assert_eq!(pins, [&FRONT_PINS[..], &BACK_PINS[..]].concat());
+    }
+}
+
+#[test]
+fn test_corner_dials_stay_linked() {
+    for seed in 0..20 {
+        let mut state = ClockState::solved();
+        state.apply_scramble(&ClockScramble::generate_seeded(seed));
+        for (front, back) in [(0, 2), (2, 0), (6, 8), (8, 6)] {
+            assert_eq!((state.front()[front] + state.back()[back]) % 12, 0);
+        }
+    }
+}
+
+#[test]
+fn test_state_display() {
+    let mut state = ClockState::solved();
+    state.apply_move(&turn(ClockPins::UpRight, 3));
+    assert_eq!(
+        state.to_string(),
+        "12  3  3     9 12 12\n12  3  3    12 12 12\n12 12 12    12 12 12"
+    );
+}
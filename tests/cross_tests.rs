@@ -0,0 +1,106 @@
+use rstest::rstest;
+use scramble_gen::generators::cross::{CrossOptions, MAX_ANY_COLOUR_DEPTH};
+use scramble_gen::scramble::seeded_rng;
+use scramble_gen::solvers::cross::MAX_DEPTH;
+use scramble_gen::solvers::three::ThreeByThreeState;
+use scramble_gen::state::FACE_ORDER;
+use scramble_gen::{MoveFace, Scramble, ScrambleError};
+
+fn state_after(scramble: &Scramble) -> ThreeByThreeState {
+    let mut state = ThreeByThreeState::solved();
+    state.apply_moves(&scramble.moves);
+    state
+}
+
+#[rstest]
+#[case("", MoveFace::Down, 0)]
+#[case("U2", MoveFace::Down, 0)]
+#[case("D2", MoveFace::Down, 1)]
+#[case("R", MoveFace::Down, 1)]
+#[case("R", MoveFace::Left, 0)]
+#[case("R U R' U'", MoveFace::Up, 4)]
+#[case("R U R' U'", MoveFace::Back, 3)]
+#[case("F2 B2 R2 L2", MoveFace::Up, 4)]
+fn test_cross_length(#[case] moves: &str, #[case] face: MoveFace, #[case] length: usize) {
+    let state = state_after(&moves.parse().unwrap());
+    assert_eq!(state.cross_length(face), length);
+}
+
+#[test]
+fn test_cross_solutions_are_optimal() {
+    let mut rng = seeded_rng(7);
+    for _ in 0..20 {
+        let state = ThreeByThreeState::random(&mut rng);
+        for face in FACE_ORDER {
+            let solution = state.solve_cross(face);
+            assert_eq!(solution.len(), state.cross_length(face));
+            assert!(solution.len() <= MAX_DEPTH);
+
+            let mut solved = state;
+            solved.apply_moves(&solution);
+            assert_eq!(solved.cross_length(face), 0);
+        }
+    }
+}
+
+#[test]
+fn test_cross_scrambles_have_the_chosen_length() {
+    let options = CrossOptions {
+        colour: Some(MoveFace::Down),
+        random_state: true,
+        ..CrossOptions::default()
+    };
+    for cross_length in 1..=MAX_DEPTH {
+        let scramble = Scramble::generate_cross_seeded(cross_length, &options, 3);
+        assert_eq!(
+            state_after(&scramble).cross_length(MoveFace::Down),
+            cross_length
+        );
+    }
+}
+
+#[test]
+fn test_cross_scrambles_reach_the_longest_cross_on_any_colour() {
+    let scramble =
+        Scramble::generate_cross_seeded(MAX_ANY_COLOUR_DEPTH, &CrossOptions::default(), 5);
+    let state = state_after(&scramble);
+    let lengths = FACE_ORDER.map(|face| state.cross_length(face));
+    assert_eq!(lengths.iter().min(), Some(&MAX_ANY_COLOUR_DEPTH));
+}
+
+#[test]
+fn test_random_move_cross_scrambles_on_any_colour() {
+    let options = CrossOptions::default();
+    assert!(!options.random_state);
+    for seed in 0..5 {
+        let scramble = Scramble::generate_cross_seeded(4, &options, seed);
+        assert_eq!(scramble.moves.len(), 20);
+        let state = state_after(&scramble);
+        let lengths = FACE_ORDER.map(|face| state.cross_length(face));
+        assert_eq!(lengths.iter().min(), Some(&4));
+    }
+}
+
+#[test]
+fn test_cross_scrambles_are_reproducible() {
+    let options = CrossOptions::default();
+    assert_eq!(
+        Scramble::generate_cross_seeded(5, &options, 11),
+        Scramble::generate_cross_seeded(5, &options, 11)
+    );
+}
+
+#[test]
+fn test_cross_attempt_limit() {
+    let options = CrossOptions {
+        max_attempts: 10,
+        ..CrossOptions::default()
+    };
+    assert_eq!(
+        Scramble::try_generate_cross_seeded(1, &options, 1),
+        Err(ScrambleError::CrossNotFound {
+            cross_length: 1,
+            attempts: 10,
+        })
+    );
+}
@@ -0,0 +1,127 @@
+use scramble_gen::error::MAX_SCRAMBLE_LENGTH;
+use scramble_gen::generators::cross::CrossOptions;
+use scramble_gen::generators::two;
+use scramble_gen::generators::validator::try_combine_moves;
+use scramble_gen::{Cube, Move, MoveFace, MoveType, MoveWidth, Scramble, ScrambleError};
+
+#[test]
+fn test_zero_length_is_an_error() {
+    assert_eq!(
+        Scramble::try_generate(Cube::ThreeByThree, Some(0)),
+        Err(ScrambleError::InvalidLength(0))
+    );
+}
+
+#[test]
+fn test_absurd_length_is_an_error() {
+    let length = MAX_SCRAMBLE_LENGTH + 1;
+    assert_eq!(
+        Scramble::try_generate_seeded(Cube::SevenBySeven, Some(length), 3),
+        Err(ScrambleError::InvalidLength(length))
+    );
+    assert!(Scramble::try_generate(Cube::SevenBySeven, Some(MAX_SCRAMBLE_LENGTH)).is_ok());
+}
+
+#[test]
+fn test_valid_lengths_generate() {
+    let scramble = Scramble::try_generate(Cube::FourByFour, None).unwrap();
+    assert_eq!(scramble.moves.len(), 40);
+    let scramble = Scramble::try_generate(Cube::TwoByTwo, Some(1)).unwrap();
+    assert_eq!(scramble.moves.len(), 1);
+}
+
+#[test]
+fn test_unsupported_random_state_is_an_error() {
+    for cube in [
+        Cube::FiveByFive,
+        Cube::SixBySix,
+        Cube::SevenBySeven,
+        Cube::NxN(9),
+    ] {
+        assert_eq!(
+            Scramble::try_generate_random_state(cube),
+            Err(ScrambleError::RandomStateUnsupported(cube))
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "random-state scrambles are not supported")]
+fn test_generate_random_state_panics_for_unsupported_cube() {
+    Scramble::generate_random_state(Cube::FiveByFive);
+}
+
+#[test]
+fn test_unsupported_blind_is_an_error() {
+    for cube in [Cube::TwoByTwo, Cube::SixBySix, Cube::NxN(9)] {
+        assert_eq!(
+            Scramble::try_generate_blind(cube),
+            Err(ScrambleError::BlindUnsupported(cube))
+        );
+    }
+    assert_eq!(
+        ScrambleError::BlindUnsupported(Cube::TwoByTwo).to_string(),
+        "blindfolded scrambles are not supported for 2x2"
+    );
+}
+
+#[test]
+fn test_invalid_min_depth_is_an_error() {
+    let result = two::try_generate_random_state_with_rng(&mut rand::rng(), Some(12));
+    assert_eq!(
+        result,
+        Err(ScrambleError::InvalidMinDepth {
+            min_depth: 12,
+            max_depth: 11,
+        })
+    );
+}
+
+#[test]
+fn test_invalid_cross_length_is_an_error() {
+    let on_d = CrossOptions {
+        colour: Some(MoveFace::Down),
+        ..CrossOptions::default()
+    };
+    assert_eq!(
+        Scramble::try_generate_cross(9, &on_d),
+        Err(ScrambleError::InvalidCrossLength {
+            cross_length: 9,
+            max_length: 8,
+        })
+    );
+    assert_eq!(
+        Scramble::try_generate_cross(0, &on_d),
+        Err(ScrambleError::InvalidCrossLength {
+            cross_length: 0,
+            max_length: 8,
+        })
+    );
+    // No scramble needs eight moves for the cross on every colour.
+    assert_eq!(
+        Scramble::try_generate_cross(8, &CrossOptions::default()),
+        Err(ScrambleError::InvalidCrossLength {
+            cross_length: 8,
+            max_length: 7,
+        })
+    );
+}
+
+#[test]
+fn test_combining_different_faces_is_an_error() {
+    let first = Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Single,
+    };
+    let second = Move {
+        move_face: MoveFace::Up,
+        ..first.clone()
+    };
+    let error = try_combine_moves(&first, &second).unwrap_err();
+    assert_eq!(error, ScrambleError::CannotCombine(first, second));
+    assert_eq!(
+        error.to_string(),
+        "cannot combine R and U: moves are on different faces or widths"
+    );
+}
@@ -0,0 +1,90 @@
+use scramble_gen::generators::three::FMC_PADDING;
+use scramble_gen::generators::validator::has_cancellation;
+use scramble_gen::solvers::three::ThreeByThreeState;
+use scramble_gen::{Move, MoveFace, MoveType, MoveWidth, Scramble};
+
+fn single(move_face: MoveFace, move_type: MoveType) -> Move {
+    Move {
+        move_face,
+        move_type,
+        move_width: MoveWidth::Single,
+    }
+}
+
+#[test]
+fn test_padding_is_r_u_f() {
+    let padding: Vec<String> = FMC_PADDING.iter().map(ToString::to_string).collect();
+    assert_eq!(padding, ["R'", "U'", "F"]);
+}
+
+#[test]
+fn test_fmc_scramble_is_padded() {
+    for seed in 0..8 {
+        let scramble = Scramble::generate_fmc_seeded(seed);
+        let moves = &scramble.moves;
+        assert!(moves.starts_with(&FMC_PADDING));
+        assert!(moves.ends_with(&FMC_PADDING));
+        assert!(moves.len() <= 2 * FMC_PADDING.len() + 22);
+        assert!(moves.iter().all(|m| m.move_width == MoveWidth::Single));
+
+        let middle = &moves[FMC_PADDING.len()..moves.len() - FMC_PADDING.len()];
+        assert!(![MoveFace::Front, MoveFace::Back].contains(&middle[0].move_face));
+        assert!(![MoveFace::Right, MoveFace::Left].contains(&middle[middle.len() - 1].move_face));
+        assert!(!has_cancellation(moves), "{scramble}");
+
+        let mut state = ThreeByThreeState::solved();
+        state.apply_moves(moves);
+        assert!(!state.is_solved());
+    }
+}
+
+#[test]
+fn test_has_cancellation() {
+    use MoveFace::*;
+    use MoveType::*;
+    for (moves, cancels) in [
+        (vec![single(Right, Normal), single(Right, Prime)], true),
+        (vec![single(Right, Normal), single(Right, Double)], true),
+        (
+            vec![
+                single(Right, Normal),
+                single(Left, Double),
+                single(Right, Normal),
+            ],
+            true,
+        ),
+        (
+            vec![
+                single(Right, Normal),
+                single(Up, Double),
+                single(Right, Normal),
+            ],
+            false,
+        ),
+        (vec![single(Front, Normal), single(Back, Normal)], false),
+        (
+            vec![
+                single(Right, Normal),
+                Move {
+                    move_width: MoveWidth::Wide,
+                    ..single(Right, Normal)
+                },
+            ],
+            false,
+        ),
+    ] {
+        assert_eq!(has_cancellation(&moves), cancels, "{moves:?}");
+    }
+}
+
+#[test]
+fn test_seeded_fmc_scrambles_are_reproducible() {
+    assert_eq!(
+        Scramble::generate_fmc_seeded(11),
+        Scramble::generate_fmc_seeded(11)
+    );
+    assert_ne!(
+        Scramble::generate_fmc_seeded(11),
+        Scramble::generate_fmc_seeded(12)
+    );
+}
@@ -0,0 +1,60 @@
+use scramble_gen::puzzles::megaminx::{LINE_LENGTH, LINES, MegaminxMove, MegaminxScramble};
+
+#[test]
+fn test_move_display() {
+    assert_eq!(MegaminxMove::Right { plus: true }.to_string(), "R++");
+    assert_eq!(MegaminxMove::Right { plus: false }.to_string(), "R--");
+    assert_eq!(MegaminxMove::Down { plus: true }.to_string(), "D++");
+    assert_eq!(MegaminxMove::Down { plus: false }.to_string(), "D--");
+    assert_eq!(MegaminxMove::Up { prime: false }.to_string(), "U");
+    assert_eq!(MegaminxMove::Up { prime: true }.to_string(), "U'");
+}
+
+#[test]
+fn test_line_structure() {
+    for seed in 0..20 {
+        let scramble = MegaminxScramble::generate_seeded(seed);
+        assert_eq!(scramble.lines.len(), LINES);
+        for line in &scramble.lines {
+            assert_eq!(line.len(), LINE_LENGTH + 1);
+            for (i, move_) in line[..LINE_LENGTH].iter().enumerate() {
+                match move_ {
+                    MegaminxMove::Right { .. } => assert_eq!(i % 2, 0),
+                    MegaminxMove::Down { .. } => assert_eq!(i % 2, 1),
+                    MegaminxMove::Up { .. } => panic!("U move inside a line"),
+                }
+            }
+        }
+        assert_eq!(scramble.moves().count(), LINES * (LINE_LENGTH + 1));
+    }
+}
+
+#[test]
+fn test_up_turn_follows_the_last_down_turn() {
+    let mut endings = Vec::new();
+    for seed in 0..20 {
+        for line in MegaminxScramble::generate_seeded(seed).to_lines() {
+            let ending = line.rsplitn(3, ' ').collect::<Vec<_>>();
+            match ending[..2] {
+                ["U", "D++"] | ["U'", "D--"] => {}
+                _ => panic!("line ends with {} {}", ending[1], ending[0]),
+            }
+            endings.push(ending[0].to_string());
+        }
+    }
+    assert!(endings.iter().any(|up| up == "U"));
+    assert!(endings.iter().any(|up| up == "U'"));
+}
+
+#[test]
+fn test_display_keeps_lines() {
+    let scramble = MegaminxScramble::generate_seeded(1);
+    let text = scramble.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines, scramble.to_lines());
+    assert_eq!(lines.len(), LINES);
+    for line in lines {
+        assert_eq!(line.split(' ').count(), LINE_LENGTH + 1);
+        assert!(line.ends_with('U') || line.ends_with("U'"));
+    }
+}
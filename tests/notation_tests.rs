@@ -0,0 +1,215 @@
+use rstest::rstest;
+use scramble_gen::notation::{NotationStyle, ParseErrorKind};
+use scramble_gen::{Cube, Move, MoveFace, MoveType, MoveWidth, ParseError, Scramble};
+
+#[rstest]
+#[case("R", MoveFace::Right, MoveType::Normal, MoveWidth::Single)]
+#[case("U'", MoveFace::Up, MoveType::Prime, MoveWidth::Single)]
+#[case("F2", MoveFace::Front, MoveType::Double, MoveWidth::Single)]
+#[case("B2'", MoveFace::Back, MoveType::Double, MoveWidth::Single)]
+#[case("Rw", MoveFace::Right, MoveType::Normal, MoveWidth::Wide)]
+#[case("Uw2", MoveFace::Up, MoveType::Double, MoveWidth::Wide)]
+#[case("2Lw'", MoveFace::Left, MoveType::Prime, MoveWidth::Wide)]
+#[case("3Rw'", MoveFace::Right, MoveType::Prime, MoveWidth::ThreeWide)]
+#[case("3D2", MoveFace::Down, MoveType::Double, MoveWidth::ThreeWide)]
+#[case("3-3D2", MoveFace::Down, MoveType::Double, MoveWidth::Slice(3))]
+#[case("2-2R'", MoveFace::Right, MoveType::Prime, MoveWidth::Slice(2))]
+#[case("r", MoveFace::Right, MoveType::Normal, MoveWidth::Wide)]
+#[case("u2", MoveFace::Up, MoveType::Double, MoveWidth::Wide)]
+#[case("3f'", MoveFace::Front, MoveType::Prime, MoveWidth::ThreeWide)]
+#[case("4Rw", MoveFace::Right, MoveType::Normal, MoveWidth::Layers(4))]
+#[case("6b2", MoveFace::Back, MoveType::Double, MoveWidth::Layers(6))]
+#[case("M", MoveFace::Left, MoveType::Normal, MoveWidth::Middle)]
+#[case("E'", MoveFace::Down, MoveType::Prime, MoveWidth::Middle)]
+#[case("S2", MoveFace::Front, MoveType::Double, MoveWidth::Middle)]
+#[case("x", MoveFace::Right, MoveType::Normal, MoveWidth::Rotation)]
+#[case("y'", MoveFace::Up, MoveType::Prime, MoveWidth::Rotation)]
+#[case("z2", MoveFace::Front, MoveType::Double, MoveWidth::Rotation)]
+fn test_parse_move(
+    #[case] input: &str,
+    #[case] move_face: MoveFace,
+    #[case] move_type: MoveType,
+    #[case] move_width: MoveWidth,
+) {
+    let parsed: Move = input.parse().unwrap();
+    assert_eq!(
+        parsed,
+        Move {
+            move_face,
+            move_type,
+            move_width,
+        }
+    );
+}
+
+#[rstest]
+#[case("D", Ok(MoveFace::Down))]
+#[case("B", Ok(MoveFace::Back))]
+#[case("", Err(ParseError { position: 0, kind: ParseErrorKind::Empty }))]
+#[case("d", Err(ParseError { position: 0, kind: ParseErrorKind::InvalidFace('d') }))]
+#[case("D2", Err(ParseError { position: 1, kind: ParseErrorKind::InvalidSuffix("2".to_string()) }))]
+fn test_parse_face(#[case] input: &str, #[case] expected: Result<MoveFace, ParseError>) {
+    assert_eq!(input.parse::<MoveFace>(), expected);
+}
+
+#[rstest]
+#[case(MoveWidth::Single, Some(1))]
+#[case(MoveWidth::ThreeWide, Some(3))]
+#[case(MoveWidth::Layers(5), Some(5))]
+#[case(MoveWidth::Slice(2), None)]
+#[case(MoveWidth::Middle, None)]
+#[case(MoveWidth::Rotation, None)]
+fn test_move_width_layers(#[case] width: MoveWidth, #[case] layers: Option<usize>) {
+    assert_eq!(width.layers(), layers);
+}
+
+#[test]
+fn test_zero_widths_turn_no_layers() {
+    assert_eq!(MoveWidth::from_layers(0), None);
+    assert!(MoveWidth::Slice(0).turned_layers(5).is_empty());
+    assert!(MoveWidth::Layers(0).turned_layers(5).is_empty());
+}
+
+#[rstest]
+#[case("", 0, ParseErrorKind::Empty)]
+#[case("X", 0, ParseErrorKind::InvalidFace('X'))]
+#[case("q", 0, ParseErrorKind::InvalidFace('q'))]
+#[case("3", 1, ParseErrorKind::Empty)]
+#[case("1Rw", 0, ParseErrorKind::UnsupportedWidth(1))]
+#[case("1R", 0, ParseErrorKind::UnsupportedWidth(1))]
+#[case("2R", 0, ParseErrorKind::UnsupportedWidth(2))]
+#[case("4R", 0, ParseErrorKind::UnsupportedWidth(4))]
+#[case("0-0R", 0, ParseErrorKind::UnsupportedWidth(0))]
+#[case("1-1R", 0, ParseErrorKind::UnsupportedWidth(1))]
+#[case("2M", 0, ParseErrorKind::UnsupportedWidth(2))]
+#[case("2-3R", 0, ParseErrorKind::UnsupportedWidth(2))]
+#[case("3-3Rw", 0, ParseErrorKind::UnsupportedWidth(3))]
+#[case("xw", 1, ParseErrorKind::InvalidSuffix("w".to_string()))]
+#[case("R3", 1, ParseErrorKind::InvalidSuffix("3".to_string()))]
+#[case("Rw'2", 2, ParseErrorKind::InvalidSuffix("'2".to_string()))]
+fn test_parse_move_errors(
+    #[case] input: &str,
+    #[case] position: usize,
+    #[case] kind: ParseErrorKind,
+) {
+    assert_eq!(input.parse::<Move>(), Err(ParseError { position, kind }));
+}
+
+#[test]
+fn test_parse_scramble_ignores_whitespace() {
+    let scramble: Scramble = "  R U'\tF2\n\n Rw   3Rw' ".parse().unwrap();
+    let displayed: Vec<String> = scramble.moves.iter().map(|m| m.to_string()).collect();
+    assert_eq!(displayed, ["R", "U'", "F2", "Rw", "3Rw'"]);
+}
+
+#[test]
+fn test_parse_empty_scramble() {
+    let scramble: Scramble = "   ".parse().unwrap();
+    assert!(scramble.moves.is_empty());
+}
+
+#[test]
+fn test_parse_scramble_error_position() {
+    let error = "R U  Q2 F".parse::<Scramble>().unwrap_err();
+    assert_eq!(error.position, 5);
+    assert_eq!(error.kind, ParseErrorKind::InvalidFace('Q'));
+    assert_eq!(error.to_string(), "invalid face 'Q' at position 5");
+
+    let error = "R U F7".parse::<Scramble>().unwrap_err();
+    assert_eq!(error.position, 5);
+}
+
+#[test]
+fn test_generated_scrambles_round_trip() {
+    for cube in [
+        Cube::TwoByTwo,
+        Cube::ThreeByThree,
+        Cube::FourByFour,
+        Cube::FiveByFive,
+        Cube::SixBySix,
+        Cube::SevenBySeven,
+        Cube::NxN(12),
+    ] {
+        let scramble = Scramble::generate(cube, None);
+        let parsed: Scramble = scramble.to_string().parse().unwrap();
+        assert_eq!(parsed, scramble);
+    }
+}
+
+#[rstest]
+#[case(MoveWidth::Single, MoveType::Normal, "R", "R")]
+#[case(MoveWidth::Single, MoveType::Prime, "R'", "R'")]
+#[case(MoveWidth::Wide, MoveType::Normal, "Rw", "r")]
+#[case(MoveWidth::Wide, MoveType::Double, "Rw2", "r2")]
+#[case(MoveWidth::ThreeWide, MoveType::Prime, "3Rw'", "3r'")]
+#[case(MoveWidth::Layers(5), MoveType::Double, "5Rw2", "5r2")]
+#[case(MoveWidth::Slice(2), MoveType::Prime, "2-2R'", "2-2R'")]
+#[case(MoveWidth::Slice(3), MoveType::Normal, "3-3R", "3-3R")]
+#[case(MoveWidth::Rotation, MoveType::Normal, "x", "x")]
+fn test_notation_styles(
+    #[case] move_width: MoveWidth,
+    #[case] move_type: MoveType,
+    #[case] wca: &str,
+    #[case] sign: &str,
+) {
+    let move_ = Move {
+        move_face: MoveFace::Right,
+        move_type,
+        move_width,
+    };
+    assert_eq!(move_.to_string(), wca);
+    assert_eq!(move_.to_notation(NotationStyle::Wca), wca);
+    assert_eq!(move_.to_notation(NotationStyle::Sign), sign);
+    assert_eq!(sign.parse::<Move>().unwrap(), move_);
+}
+
+#[test]
+fn test_move_width_display() {
+    assert_eq!(MoveWidth::Single.to_string(), "");
+    assert_eq!(MoveWidth::Wide.to_string(), "w");
+    assert_eq!(MoveWidth::ThreeWide.to_string(), "3w");
+    assert_eq!(MoveWidth::Layers(4).to_string(), "4w");
+    assert_eq!(MoveWidth::Slice(2).to_string(), "2-2");
+    assert_eq!(MoveWidth::Slice(3).to_string(), "3-3");
+    assert_eq!(MoveWidth::Middle.to_string(), "");
+}
+
+#[rstest]
+#[case(MoveFace::Right, MoveType::Normal, MoveWidth::Middle, "M'")]
+#[case(MoveFace::Up, MoveType::Double, MoveWidth::Middle, "E2")]
+#[case(MoveFace::Back, MoveType::Prime, MoveWidth::Middle, "S")]
+#[case(MoveFace::Left, MoveType::Normal, MoveWidth::Rotation, "x'")]
+#[case(MoveFace::Down, MoveType::Prime, MoveWidth::Rotation, "y")]
+#[case(MoveFace::Back, MoveType::Normal, MoveWidth::Rotation, "z'")]
+fn test_axis_moves_display_from_their_face(
+    #[case] move_face: MoveFace,
+    #[case] move_type: MoveType,
+    #[case] move_width: MoveWidth,
+    #[case] expected: &str,
+) {
+    let move_ = Move {
+        move_face,
+        move_type,
+        move_width,
+    };
+    assert_eq!(move_.to_string(), expected);
+}
+
+#[test]
+fn test_slices_and_rotations_round_trip() {
+    let input = "x y' z2 M E' S2 2-2R 3Uw' 4-4F2 ";
+    assert_eq!(input.parse::<Scramble>().unwrap().to_string(), input);
+}
+
+#[test]
+fn test_scramble_notation_styles_round_trip() {
+    let scramble = Scramble::generate(Cube::SevenBySeven, None);
+    assert_eq!(
+        scramble.to_notation(NotationStyle::Wca),
+        scramble.to_string()
+    );
+
+    let sign = scramble.to_notation(NotationStyle::Sign);
+    assert!(!sign.contains('w'));
+    assert_eq!(sign.parse::<Scramble>().unwrap(), scramble);
+}
@@ -0,0 +1,91 @@
+use scramble_gen::puzzles::pyraminx::{
+    DEFAULT_MIN_DEPTH, MAX_DEPTH, PyraminxAxis, PyraminxMove, PyraminxScramble, PyraminxState,
+};
+
+const AXES: [PyraminxAxis; 4] = [
+    PyraminxAxis::Up,
+    PyraminxAxis::Left,
+    PyraminxAxis::Right,
+    PyraminxAxis::Back,
+];
+
+fn turn(axis: PyraminxAxis, tip: bool, prime: bool) -> PyraminxMove {
+    PyraminxMove { axis, tip, prime }
+}
+
+#[test]
+fn test_move_display() {
+    assert_eq!(turn(PyraminxAxis::Up, false, false).to_string(), "U");
+    assert_eq!(turn(PyraminxAxis::Left, false, true).to_string(), "L'");
+    assert_eq!(turn(PyraminxAxis::Right, true, false).to_string(), "r");
+    assert_eq!(turn(PyraminxAxis::Back, true, true).to_string(), "b'");
+}
+
+#[test]
+fn test_tip_moves_only_move_tips() {
+    let mut state = PyraminxState::solved();
+    for axis in AXES {
+        state.apply_move(&turn(axis, true, false));
+    }
+    assert!(!state.is_solved());
+    assert_eq!(state.distance(), 0);
+    let changed = state
+        .stickers()
+        .iter()
+        .zip(PyraminxState::solved().stickers())
+        .filter(|(a, b)| a != b)
+        .count();
+    assert_eq!(changed, 12);
+}
+
+#[test]
+fn test_turns_carry_their_tips() {
+    for axis in AXES {
+        // A corner turn takes its tip along, so no tip move is needed after
+        // undoing it.
+        let mut state = PyraminxState::solved();
+        state.apply_move(&turn(axis, false, false));
+        assert_eq!(state.solve(), vec![turn(axis, false, true)]);
+
+        // Turning the tip back leaves only the layer below it turned.
+        state.apply_move(&turn(axis, true, true));
+        assert_eq!(
+            state.solve(),
+            vec![turn(axis, false, true), turn(axis, true, false)]
+        );
+    }
+}
+
+#[test]
+fn test_random_state_scrambles() {
+    for seed in 0..50 {
+        let scramble = PyraminxScramble::generate_seeded(seed);
+        let turns = scramble.moves.iter().take_while(|m| !m.tip).count();
+        assert!((DEFAULT_MIN_DEPTH..=MAX_DEPTH).contains(&turns));
+
+        // Tips come last, at most one per corner, in U L R B order.
+        let tips: Vec<usize> = scramble.moves[turns..]
+            .iter()
+            .map(|m| {
+                assert!(m.tip, "{scramble}");
+                AXES.iter().position(|&axis| axis == m.axis).unwrap()
+            })
+            .collect();
+        assert!(tips.windows(2).all(|w| w[0] < w[1]), "{scramble}");
+
+        let mut state = PyraminxState::solved();
+        state.apply_scramble(&scramble);
+        assert_eq!(state.distance(), turns);
+
+        // The solution is optimal for the non-tip pieces and fixes the tips
+        // last.
+        let solution = state.solve();
+        let solution_turns = solution.iter().take_while(|m| !m.tip).count();
+        assert_eq!(solution_turns, turns);
+        assert!(solution[solution_turns..].iter().all(|m| m.tip));
+        for move_ in &solution {
+            state.apply_move(move_);
+        }
+        assert!(state.is_solved());
+    }
+}
@@ -1,6 +1,6 @@
 use rstest::rstest;
 use scramble_gen::cube::Cube;
-use scramble_gen::generators::validator::{can_combine, combine_moves};
+use scramble_gen::generators::validator::{can_combine, collapses, combine_moves};
 use scramble_gen::moves::{Move, MoveFace, MoveType, MoveWidth};
 use scramble_gen::scramble::Scramble;
 
@@ -56,6 +56,16 @@ fn verify_no_parallel_conflicts(scramble: &Scramble) -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn test_no_redundant_moves_2x2() {
+    for i in 0..1000 {
+        let scramble = Scramble::generate(Cube::TwoByTwo, Some(11));
+        if let Err(e) = verify_no_redundant_moves(&scramble) {
+            panic!("2x2 scramble {i} failed: {e}");
+        }
+    }
+}
+
 #[test]
 fn test_no_redundant_moves_3x3() {
     for i in 0..1000 {
@@ -121,6 +131,19 @@ fn test_no_redundant_moves_7x7() {
     }
 }
 
+#[test]
+fn test_no_redundant_moves_nxn() {
+    for i in 0..200 {
+        let scramble = Scramble::generate(Cube::NxN(10), None);
+        if let Err(e) = verify_no_redundant_moves(&scramble) {
+            panic!("10x10 scramble {i} failed: {e}");
+        }
+        if let Err(e) = verify_no_parallel_conflicts(&scramble) {
+            panic!("10x10 scramble {i} failed: {e}");
+        }
+    }
+}
+
 #[test]
 fn test_combine_moves_normal_normal() {
     let move1 = Move {
@@ -223,6 +246,28 @@ fn test_can_combine_different_widths() {
     assert!(!can_combine(&move1, &move2));
 }
 
+#[test]
+fn test_can_combine_a_width_written_with_its_layers() {
+    let wide = Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Wide,
+    };
+    let two_layers = Move {
+        move_width: MoveWidth::Layers(2),
+        ..wide.clone()
+    };
+
+    assert!(can_combine(&wide, &two_layers));
+    assert_eq!(
+        combine_moves(&two_layers, &two_layers),
+        Some(Move {
+            move_type: MoveType::Double,
+            ..wide
+        })
+    );
+}
+
 #[test]
 fn test_same_axis_detection() {
     assert!(MoveFace::Right.same_axis(&MoveFace::Left));
@@ -292,3 +337,100 @@ fn test_parallel_conflict_patterns(
         );
     }
 }
+
+#[rstest]
+#[case("R", "R'", 3, true)]
+#[case("R", "L", 3, false)]
+#[case("R", "U", 3, false)]
+#[case("R", "L", 2, true)]
+#[case("Rw", "Lw'", 4, true)]
+#[case("Rw", "Lw'", 5, false)]
+#[case("R", "Rw", 4, false)]
+#[case("R", "2-2R", 4, true)]
+#[case("Rw", "2-2R", 4, true)]
+#[case("3Rw", "3Lw", 6, true)]
+#[case("3Rw", "2Lw", 6, false)]
+#[case("M", "R", 3, true)]
+#[case("U", "y", 3, true)]
+#[case("4Rw", "U", 4, false)]
+#[case("4Rw", "R", 4, true)]
+fn test_collapses(
+    #[case] first: &str,
+    #[case] second: &str,
+    #[case] layers: usize,
+    #[case] expected: bool,
+) {
+    let (first, second): (Move, Move) = (first.parse().unwrap(), second.parse().unwrap());
+    assert_eq!(collapses(&first, &second, layers), expected);
+    assert_eq!(collapses(&second, &first, layers), expected);
+}
+
+/// Every pair of moves in each block of moves on one axis, e.g. `Rw Lw'`
+/// in `U Rw Lw' F`.
+fn block_pairs(scramble: &Scramble) -> Vec<(Move, Move)> {
+    let mut pairs = Vec::new();
+    for block in scramble.moves.chunk_by(|first, second| {
+        first.move_face == second.move_face || first.move_face.same_axis(&second.move_face)
+    }) {
+        for (i, first) in block.iter().enumerate() {
+            for second in &block[i + 1..] {
+                pairs.push((first.clone(), second.clone()));
+            }
+        }
+    }
+    pairs
+}
+
+#[test]
+fn test_big_cube_blocks_never_collapse() {
+    for cube in [
+        Cube::FourByFour,
+        Cube::FiveByFive,
+        Cube::SixBySix,
+        Cube::SevenBySeven,
+        Cube::NxN(8),
+        Cube::NxN(11),
+    ] {
+        for seed in 0..50 {
+            let scramble = Scramble::generate_seeded(cube, None, seed);
+            for (first, second) in block_pairs(&scramble) {
+                assert!(
+                    !collapses(&first, &second, cube.layers()),
+                    "{cube:?} scramble {scramble} has {first} and {second} in one block"
+                );
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case(Cube::FourByFour, MoveWidth::Wide)]
+#[case(Cube::SixBySix, MoveWidth::ThreeWide)]
+#[case(Cube::NxN(10), MoveWidth::Layers(5))]
+fn test_even_cubes_turn_widest_layers_from_r_u_f(#[case] cube: Cube, #[case] widest: MoveWidth) {
+    let mut faces = Vec::new();
+    for seed in 0..50 {
+        let scramble = Scramble::generate_seeded(cube, None, seed);
+        for move_ in scramble.moves {
+            if move_.move_width == widest && !faces.contains(&move_.move_face) {
+                faces.push(move_.move_face);
+            }
+        }
+    }
+    faces.sort_by_key(|&face| face as usize);
+    assert_eq!(faces, [MoveFace::Right, MoveFace::Up, MoveFace::Front]);
+}
+
+#[test]
+fn test_odd_cubes_turn_widest_layers_from_every_face() {
+    let mut faces = Vec::new();
+    for seed in 0..50 {
+        let scramble = Scramble::generate_seeded(Cube::SevenBySeven, None, seed);
+        for move_ in scramble.moves {
+            if move_.move_width == MoveWidth::ThreeWide && !faces.contains(&move_.move_face) {
+                faces.push(move_.move_face);
+            }
+        }
+    }
+    assert_eq!(faces.len(), 6);
+}
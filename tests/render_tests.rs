@@ -0,0 +1,126 @@
+use scramble_gen::puzzles::clock::{ClockMove, ClockPins, ClockScramble, ClockState};
+use scramble_gen::render::{ColourScheme, SvgOptions, render_clock_svg, render_svg};
+use scramble_gen::{Cube, CubeState, Move, MoveFace, MoveType, MoveWidth, Scramble};
+
+const CUBES: [Cube; 6] = [
+    Cube::TwoByTwo,
+    Cube::ThreeByThree,
+    Cube::FourByFour,
+    Cube::FiveByFive,
+    Cube::SixBySix,
+    Cube::SevenBySeven,
+];
+
+fn count_fill(svg: &str, colour: &str) -> usize {
+    svg.matches(&format!(r#"fill="{colour}""#)).count()
+}
+
+#[test]
+fn test_svg_has_one_rect_per_sticker() {
+    for cube in CUBES {
+        let state = CubeState::from_scramble(cube, &Scramble::generate(cube, None));
+        let svg = render_svg(&state, &SvgOptions::default());
+        let n = cube.layers();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect ").count(), 6 * n * n);
+    }
+}
+
+#[test]
+fn test_svg_uses_every_colour_equally() {
+    let state = CubeState::from_scramble(
+        Cube::FiveByFive,
+        &Scramble::generate(Cube::FiveByFive, None),
+    );
+    let svg = render_svg(&state, &SvgOptions::default());
+    let colours = ColourScheme::default();
+    for face in [
+        MoveFace::Up,
+        MoveFace::Right,
+        MoveFace::Front,
+        MoveFace::Down,
+        MoveFace::Left,
+        MoveFace::Back,
+    ] {
+        assert_eq!(count_fill(&svg, colours.colour(face)), 25);
+    }
+}
+
+#[test]
+fn test_svg_size_follows_sticker_size() {
+    let state = CubeState::new(Cube::ThreeByThree);
+    let options = SvgOptions {
+        sticker_size: 10,
+        face_gap: 2,
+        ..SvgOptions::default()
+    };
+    let svg = render_svg(&state, &options);
+    assert!(svg.contains(r#"width="130" height="98""#));
+    assert!(svg.contains(r#"width="10" height="10""#));
+}
+
+#[test]
+fn test_svg_custom_colours() {
+    let mut state = CubeState::new(Cube::ThreeByThree);
+    state.apply_move(&Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Single,
+    });
+    let options = SvgOptions {
+        colours: ColourScheme {
+            up: "black".to_string(),
+            front: "pink".to_string(),
+            ..ColourScheme::default()
+        },
+        ..SvgOptions::default()
+    };
+    let svg = render_svg(&state, &options);
+    assert_eq!(count_fill(&svg, "black"), 9);
+    assert_eq!(count_fill(&svg, "pink"), 9);
+    assert_eq!(count_fill(&svg, "#ffffff"), 0);
+}
+
+#[test]
+fn test_svg_escapes_colours() {
+    let options = SvgOptions {
+        colours: ColourScheme {
+            up: r#"red" onload="alert(1)"#.to_string(),
+            down: "</svg><script>".to_string(),
+            ..ColourScheme::default()
+        },
+        ..SvgOptions::default()
+    };
+    let svg = render_svg(&CubeState::new(Cube::ThreeByThree), &options);
+    assert_eq!(count_fill(&svg, "red&quot; onload=&quot;alert(1)"), 9);
+    assert_eq!(count_fill(&svg, "&lt;/svg&gt;&lt;script&gt;"), 9);
+    assert!(!svg.contains("onload=\""));
+    assert!(!svg.contains("<script>"));
+    assert_eq!(svg.matches("</svg>").count(), 1);
+}
+
+#[test]
+fn test_clock_svg_draws_every_dial() {
+    let mut state = ClockState::solved();
+    state.apply_scramble(&ClockScramble::generate_seeded(1));
+    let svg = render_clock_svg(&state, &SvgOptions::default());
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<circle ").count(), 18);
+    assert_eq!(svg.matches("<line ").count(), 18);
+}
+
+#[test]
+fn test_clock_svg_hands_point_at_hours() {
+    let mut state = ClockState::solved();
+    let svg = render_clock_svg(&state, &SvgOptions::default());
+    assert!(svg.contains(r#"x1="14.0" y1="14.0" x2="14.0" y2="7.6""#));
+
+    state.apply_move(&ClockMove::Turn {
+        pins: ClockPins::UpLeft,
+        amount: 3,
+    });
+    let svg = render_clock_svg(&state, &SvgOptions::default());
+    assert!(svg.contains(r#"x1="14.0" y1="14.0" x2="20.4" y2="14.0""#));
+}
@@ -1,4 +1,28 @@
-use scramble_gen::{Cube, Move, MoveFace, MoveType, MoveWidth, Scramble};
+use scramble_gen::{Cube, Move, MoveFace, MoveType, MoveWidth, Scramble, ScrambleError};
+
+#[test]
+fn test_2x2_scramble_default_length() {
+    let scramble = Scramble::generate(Cube::TwoByTwo, None);
+    assert_eq!(scramble.moves.len(), 11);
+}
+
+#[test]
+fn test_2x2_scramble_custom_length() {
+    let scramble = Scramble::generate(Cube::TwoByTwo, Some(15));
+    assert_eq!(scramble.moves.len(), 15);
+}
+
+#[test]
+fn test_2x2_moves_are_r_u_f_only() {
+    let scramble = Scramble::generate(Cube::TwoByTwo, Some(100));
+    for move_ in &scramble.moves {
+        assert!(matches!(
+            move_.move_face,
+            MoveFace::Right | MoveFace::Up | MoveFace::Front
+        ));
+        assert!(matches!(move_.move_width, MoveWidth::Single));
+    }
+}
 
 #[test]
 fn test_3x3_scramble_default_length() {
@@ -165,5 +189,89 @@ fn test_three_wide_display() {
         move_type: MoveType::Prime,
         move_width: MoveWidth::ThreeWide,
     };
-    assert_eq!(format!("{three_wide_move}"), "3R'");
+    assert_eq!(format!("{three_wide_move}"), "3Rw'");
+}
+
+#[test]
+fn test_cube_from_layers() {
+    assert_eq!(Cube::from_layers(2), Cube::TwoByTwo);
+    assert_eq!(Cube::from_layers(7), Cube::SevenBySeven);
+    assert_eq!(Cube::from_layers(8), Cube::NxN(8));
+    assert_eq!(Cube::NxN(13).layers(), 13);
+    assert_eq!(Cube::NxN(13).to_string(), "13x13");
+    assert_eq!(Cube::FourByFour.to_string(), "4x4");
+}
+
+#[test]
+fn test_nxn_default_lengths() {
+    for (layers, length) in [(8, 120), (9, 140), (13, 220), (52, 1000), (60, 1000)] {
+        let scramble = Scramble::generate(Cube::NxN(layers), None);
+        assert_eq!(scramble.moves.len(), length);
+    }
+}
+
+#[test]
+fn test_nxn_with_named_size_uses_named_generator() {
+    assert_eq!(
+        Scramble::generate_seeded(Cube::NxN(5), None, 8),
+        Scramble::generate_seeded(Cube::FiveByFive, None, 8)
+    );
+    assert_eq!(Scramble::generate(Cube::NxN(2), None).moves.len(), 11);
+}
+
+#[test]
+fn test_nxn_widths_stay_below_the_middle() {
+    for layers in [8, 9, 12] {
+        let scramble = Scramble::generate(Cube::NxN(layers), Some(500));
+        let widest = scramble
+            .moves
+            .iter()
+            .map(|m| m.move_width.layers().unwrap())
+            .max()
+            .unwrap();
+        assert_eq!(widest, layers / 2);
+        assert!(
+            scramble
+                .moves
+                .iter()
+                .any(|m| m.move_width == MoveWidth::Single)
+        );
+    }
+}
+
+#[test]
+fn test_nxn_uses_named_widths_below_four_layers() {
+    let scramble = Scramble::generate(Cube::NxN(10), Some(300));
+    for move_ in &scramble.moves {
+        assert_eq!(
+            Some(move_.move_width),
+            MoveWidth::from_layers(move_.move_width.layers().unwrap())
+        );
+    }
+    assert!(
+        scramble
+            .moves
+            .iter()
+            .any(|m| matches!(m.move_width, MoveWidth::Layers(_)))
+    );
+}
+
+#[test]
+fn test_too_small_cube_is_an_error() {
+    for layers in [0, 1] {
+        assert_eq!(
+            Scramble::try_generate(Cube::NxN(layers), None),
+            Err(ScrambleError::InvalidCubeSize(layers))
+        );
+    }
+}
+
+#[test]
+fn test_layers_move_display() {
+    let move_ = Move {
+        move_face: MoveFace::Up,
+        move_type: MoveType::Prime,
+        move_width: MoveWidth::Layers(4),
+    };
+    assert_eq!(format!("{move_}"), "4Uw'");
 }
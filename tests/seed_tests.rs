@@ -0,0 +1,72 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use scramble_gen::scramble::seeded_rng;
+use scramble_gen::{Cube, Scramble};
+
+const CUBES: [Cube; 6] = [
+    Cube::TwoByTwo,
+    Cube::ThreeByThree,
+    Cube::FourByFour,
+    Cube::FiveByFive,
+    Cube::SixBySix,
+    Cube::SevenBySeven,
+];
+
+#[test]
+fn test_same_seed_gives_same_scramble() {
+    for cube in CUBES {
+        for seed in [0, 1, 42, u64::MAX] {
+            assert_eq!(
+                Scramble::generate_seeded(cube, None, seed),
+                Scramble::generate_seeded(cube, None, seed)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_different_seeds_give_different_scrambles() {
+    for cube in CUBES {
+        assert_ne!(
+            Scramble::generate_seeded(cube, Some(25), 1),
+            Scramble::generate_seeded(cube, Some(25), 2)
+        );
+    }
+}
+
+#[test]
+fn test_seeded_scramble_respects_length() {
+    let scramble = Scramble::generate_seeded(Cube::FiveByFive, Some(33), 7);
+    assert_eq!(scramble.moves.len(), 33);
+}
+
+#[test]
+fn test_seeded_scramble_matches_seeded_rng() {
+    let mut rng = seeded_rng(99);
+    assert_eq!(
+        Scramble::generate_with_rng(Cube::ThreeByThree, None, &mut rng),
+        Scramble::generate_seeded(Cube::ThreeByThree, None, 99)
+    );
+}
+
+#[test]
+fn test_caller_supplied_rng_is_deterministic() {
+    let mut first = StdRng::seed_from_u64(5);
+    let mut second = StdRng::seed_from_u64(5);
+    for _ in 0..3 {
+        assert_eq!(
+            Scramble::generate_with_rng(Cube::FourByFour, None, &mut first),
+            Scramble::generate_with_rng(Cube::FourByFour, None, &mut second)
+        );
+    }
+}
+
+#[test]
+fn test_random_state_seeded() {
+    for cube in [Cube::TwoByTwo, Cube::ThreeByThree, Cube::FourByFour] {
+        assert_eq!(
+            Scramble::generate_random_state_seeded(cube, 12),
+            Scramble::generate_random_state_seeded(cube, 12)
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use rstest::rstest;
+use scramble_gen::{Cube, CubeState, Move, MoveFace, MoveType, MoveWidth, Scramble};
+
+fn scramble(moves: &str) -> Scramble {
+    moves.parse().unwrap()
+}
+
+#[rstest]
+#[case("R L R'", "L")]
+#[case("Rw R Rw'", "R")]
+#[case("R L R L", "R2 L2")]
+#[case("R2 L' R2", "L'")]
+#[case("R U U' R'", "")]
+#[case("F B' 2-2F S F'", "B' 2-2F S")]
+#[case("M' x M' x'", "M2")]
+#[case("R U R", "R U R")]
+#[case("R U L U' R'", "R U L U' R'")]
+#[case("3Rw 3-3R 3Rw2", "3Rw' 3-3R")]
+fn test_simplify(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(scramble(input).simplify(), scramble(expected));
+}
+
+#[test]
+fn test_simplify_merges_both_ways_of_writing_a_move() {
+    let mut scramble = scramble("M");
+    scramble.moves.push(Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Prime,
+        move_width: MoveWidth::Middle,
+    });
+    assert_eq!(scramble.simplify().to_string(), "M2 ");
+
+    scramble = "Rw".parse().unwrap();
+    scramble.moves.push(Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Layers(2),
+    });
+    assert_eq!(scramble.simplify().to_string(), "Rw2 ");
+}
+
+#[test]
+fn test_scramble_and_inverse_simplify_away() {
+    for cube in [Cube::ThreeByThree, Cube::FourByFour, Cube::SevenBySeven] {
+        let scramble = Scramble::generate_seeded(cube, None, 4);
+        let mut moves = scramble.moves.clone();
+        moves.extend(scramble.inverse().moves);
+        assert!(Scramble { moves }.simplify().moves.is_empty());
+    }
+}
+
+#[test]
+fn test_generated_scrambles_are_already_simple() {
+    for cube in [Cube::TwoByTwo, Cube::ThreeByThree, Cube::SixBySix] {
+        for seed in 0..10 {
+            let scramble = Scramble::generate_seeded(cube, None, seed);
+            assert_eq!(scramble.simplify(), scramble);
+        }
+    }
+}
+
+#[test]
+fn test_simplify_keeps_the_state() {
+    let input = scramble("R L2 Rw' R' L x U D' 2-2U E U2 Uw D F M F' Bw2 B");
+    let simplified = input.simplify();
+    assert!(simplified.moves.len() < input.moves.len());
+    assert_eq!(
+        CubeState::from_scramble(Cube::FiveByFive, &simplified),
+        CubeState::from_scramble(Cube::FiveByFive, &input)
+    );
+}
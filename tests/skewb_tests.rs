@@ -0,0 +1,113 @@
+use scramble_gen::puzzles::skewb::{
+    DEFAULT_MIN_DEPTH, MAX_DEPTH, SkewbAxis, SkewbMove, SkewbScramble, SkewbState,
+};
+
+const AXES: [SkewbAxis; 4] = [
+    SkewbAxis::Right,
+    SkewbAxis::Up,
+    SkewbAxis::Left,
+    SkewbAxis::Back,
+];
+
+fn turn(axis: SkewbAxis, prime: bool) -> SkewbMove {
+    SkewbMove { axis, prime }
+}
+
+#[test]
+fn test_move_display() {
+    assert_eq!(turn(SkewbAxis::Right, false).to_string(), "R");
+    assert_eq!(turn(SkewbAxis::Up, true).to_string(), "U'");
+    assert_eq!(turn(SkewbAxis::Left, false).to_string(), "L");
+    assert_eq!(turn(SkewbAxis::Back, true).to_string(), "B'");
+}
+
+#[test]
+fn test_turn_moves_half_the_puzzle() {
+    // Three centres and four corners move, each corner showing three stickers.
+    for axis in AXES {
+        let mut state = SkewbState::solved();
+        state.apply_move(&turn(axis, false));
+        let changed = state
+            .stickers()
+            .iter()
+            .zip(SkewbState::solved().stickers())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(changed, 3 + 4 * 3);
+    }
+}
+
+#[test]
+fn test_up_front_right_corner_is_fixed() {
+    // U face bottom right, R face top left and F face top right stickers.
+    let mut state = SkewbState::solved();
+    for move_ in SkewbScramble::generate_seeded(9).moves {
+        state.apply_move(&move_);
+    }
+    assert_eq!(state.stickers()[4], 0);
+    assert_eq!(state.stickers()[6], 1);
+    assert_eq!(state.stickers()[12], 2);
+}
+
+#[test]
+fn test_turns_are_around_wca_corners() {
+    // Stickers of the turned corner, then of the corner opposite it, each
+    // with the face it shows when solved.
+    let corners = [
+        (
+            SkewbAxis::Right,
+            [(19, 3), (9, 1), (28, 5)],
+            [(3, 0), (22, 4), (11, 2)],
+        ),
+        (
+            SkewbAxis::Up,
+            [(1, 0), (21, 4), (27, 5)],
+            [(17, 3), (8, 1), (14, 2)],
+        ),
+        (
+            SkewbAxis::Left,
+            [(16, 3), (24, 4), (13, 2)],
+            [(2, 0), (7, 1), (26, 5)],
+        ),
+        (
+            SkewbAxis::Back,
+            [(18, 3), (23, 4), (29, 5)],
+            [(4, 0), (6, 1), (12, 2)],
+        ),
+    ];
+    for (axis, turned, opposite) in corners {
+        let mut state = SkewbState::solved();
+        state.apply_move(&turn(axis, false));
+        let stickers = state.stickers();
+
+        // The turned corner twists in place.
+        let mut faces: Vec<u8> = turned.iter().map(|&(i, _)| stickers[i]).collect();
+        assert!(turned.iter().all(|&(i, face)| stickers[i] != face));
+        faces.sort();
+        let mut solved: Vec<u8> = turned.iter().map(|&(_, face)| face).collect();
+        solved.sort();
+        assert_eq!(faces, solved);
+
+        assert!(opposite.iter().all(|&(i, face)| stickers[i] == face));
+    }
+}
+
+#[test]
+fn test_random_state_scrambles() {
+    for seed in 0..50 {
+        let scramble = SkewbScramble::generate_seeded(seed);
+        assert!(scramble.moves.len() >= DEFAULT_MIN_DEPTH);
+        assert!(scramble.moves.len() <= MAX_DEPTH);
+
+        let mut state = SkewbState::solved();
+        state.apply_scramble(&scramble);
+        assert_eq!(state.distance(), scramble.moves.len());
+
+        let solution = state.solve();
+        assert_eq!(solution.len(), scramble.moves.len());
+        for move_ in &solution {
+            state.apply_move(move_);
+        }
+        assert!(state.is_solved());
+    }
+}
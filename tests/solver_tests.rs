@@ -0,0 +1,269 @@
+use scramble_gen::generators::two;
+use scramble_gen::solvers::four::FourByFourState;
+use scramble_gen::solvers::three::{DEFAULT_MAX_LENGTH, ThreeByThreeState};
+use scramble_gen::solvers::two::{MAX_DEPTH, TwoByTwoState};
+use scramble_gen::{Cube, CubeState, Move, MoveFace, MoveType, MoveWidth, Scramble};
+
+fn single(move_face: MoveFace, move_type: MoveType) -> Move {
+    Move {
+        move_face,
+        move_type,
+        move_width: MoveWidth::Single,
+    }
+}
+
+#[test]
+fn test_2x2_solved_state() {
+    let state = TwoByTwoState::solved();
+    assert!(state.is_solved());
+    assert_eq!(state.distance(), 0);
+    assert!(state.solve().is_empty());
+}
+
+#[test]
+fn test_2x2_four_quarter_turns_restore_state() {
+    for face in [MoveFace::Right, MoveFace::Up, MoveFace::Front] {
+        let mut state = TwoByTwoState::solved();
+        for _ in 0..4 {
+            state.apply_move(&single(face, MoveType::Normal));
+        }
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_2x2_sexy_move_has_order_six() {
+    let sexy = [
+        single(MoveFace::Right, MoveType::Normal),
+        single(MoveFace::Up, MoveType::Normal),
+        single(MoveFace::Right, MoveType::Prime),
+        single(MoveFace::Up, MoveType::Prime),
+    ];
+    let mut state = TwoByTwoState::solved();
+    for i in 1..=6 {
+        state.apply_moves(&sexy);
+        assert_eq!(state.is_solved(), i == 6);
+    }
+}
+
+#[test]
+fn test_2x2_solver_is_optimal_for_short_sequences() {
+    let mut state = TwoByTwoState::solved();
+    state.apply_moves(&[
+        single(MoveFace::Right, MoveType::Normal),
+        single(MoveFace::Up, MoveType::Double),
+        single(MoveFace::Front, MoveType::Prime),
+    ]);
+    assert_eq!(state.distance(), 3);
+
+    let solution = state.solve();
+    assert_eq!(solution.len(), 3);
+    state.apply_moves(&solution);
+    assert!(state.is_solved());
+}
+
+#[test]
+fn test_2x2_random_states_are_solved() {
+    let mut rng = rand::rng();
+    for _ in 0..100 {
+        let mut state = TwoByTwoState::random(&mut rng);
+        let solution = state.solve();
+        assert_eq!(solution.len(), state.distance());
+        assert!(solution.len() <= MAX_DEPTH);
+        state.apply_moves(&solution);
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_2x2_random_state_scramble_reaches_min_depth() {
+    for _ in 0..100 {
+        let scramble = two::generate_random_state(Some(6));
+        let mut state = TwoByTwoState::solved();
+        state.apply_moves(&scramble.moves);
+        assert!(state.distance() >= 6);
+        assert_eq!(scramble.moves.len(), state.distance());
+    }
+}
+
+#[test]
+fn test_2x2_random_state_through_scramble() {
+    let scramble = Scramble::generate_random_state(Cube::TwoByTwo);
+    assert!(scramble.moves.len() >= 4);
+    assert!(scramble.moves.len() <= MAX_DEPTH);
+}
+
+#[test]
+fn test_3x3_solved_state() {
+    let state = ThreeByThreeState::solved();
+    assert!(state.is_solved());
+    assert_eq!(state.solve(20), Some(Vec::new()));
+}
+
+#[test]
+fn test_3x3_sexy_move_has_order_six() {
+    let mut state = ThreeByThreeState::solved();
+    let sexy = [
+        single(MoveFace::Right, MoveType::Normal),
+        single(MoveFace::Up, MoveType::Normal),
+        single(MoveFace::Right, MoveType::Prime),
+        single(MoveFace::Up, MoveType::Prime),
+    ];
+    for i in 1..=6 {
+        state.apply_moves(&sexy);
+        assert_eq!(state.is_solved(), i == 6);
+    }
+}
+
+#[test]
+fn test_3x3_solver_finds_short_solutions() {
+    let mut state = ThreeByThreeState::solved();
+    state.apply_moves(&[
+        single(MoveFace::Right, MoveType::Normal),
+        single(MoveFace::Up, MoveType::Prime),
+        single(MoveFace::Front, MoveType::Double),
+        single(MoveFace::Left, MoveType::Normal),
+        single(MoveFace::Back, MoveType::Prime),
+        single(MoveFace::Down, MoveType::Double),
+    ]);
+
+    let solution = state.solve(20).expect("state is six moves from solved");
+    state.apply_moves(&solution);
+    assert!(state.is_solved());
+}
+
+#[test]
+fn test_3x3_random_states_are_solved() {
+    let mut rng = rand::rng();
+    for _ in 0..5 {
+        let mut state = ThreeByThreeState::random(&mut rng);
+        let solution = state
+            .solve(DEFAULT_MAX_LENGTH)
+            .expect("two-phase finds a solution");
+        assert!(solution.len() <= DEFAULT_MAX_LENGTH);
+        state.apply_moves(&solution);
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_3x3_random_state_scramble_scrambles_the_cube() {
+    for _ in 0..5 {
+        let scramble = Scramble::generate_random_state(Cube::ThreeByThree);
+        assert!(scramble.moves.len() <= DEFAULT_MAX_LENGTH);
+        assert!(
+            scramble
+                .moves
+                .iter()
+                .all(|m| m.move_width == MoveWidth::Single)
+        );
+
+        let mut state = ThreeByThreeState::solved();
+        state.apply_moves(&scramble.moves);
+        assert!(!state.is_solved());
+
+        let solution = state.solve(DEFAULT_MAX_LENGTH).unwrap();
+        state.apply_moves(&solution);
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_3x3_solve_avoiding_keeps_ends_off_the_axes() {
+    let mut rng = rand::rng();
+    for _ in 0..5 {
+        let mut state = ThreeByThreeState::random(&mut rng);
+        let solution = (DEFAULT_MAX_LENGTH..)
+            .find_map(|max| state.solve_avoiding(max, Some(MoveFace::Left), Some(MoveFace::Up)))
+            .unwrap();
+        let (first, last) = (&solution[0], &solution[solution.len() - 1]);
+        assert!(![MoveFace::Right, MoveFace::Left].contains(&first.move_face));
+        assert!(![MoveFace::Up, MoveFace::Down].contains(&last.move_face));
+        state.apply_moves(&solution);
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_4x4_solved_state() {
+    let state = FourByFourState::solved();
+    assert!(state.is_solved());
+    assert!(state.solve().is_empty());
+}
+
+#[test]
+fn test_4x4_wide_sexy_move_has_order_six() {
+    let wide = |move_face, move_type| Move {
+        move_face,
+        move_type,
+        move_width: MoveWidth::Wide,
+    };
+    let sexy = [
+        wide(MoveFace::Right, MoveType::Normal),
+        wide(MoveFace::Up, MoveType::Normal),
+        wide(MoveFace::Right, MoveType::Prime),
+        wide(MoveFace::Up, MoveType::Prime),
+    ];
+    let mut state = FourByFourState::solved();
+    for i in 1..=6 {
+        state.apply_moves(&sexy);
+        assert_eq!(state.is_solved(), i == 6);
+    }
+}
+
+#[test]
+#[should_panic(expected = "4x4 state only supports single-layer and wide moves")]
+fn test_4x4_state_rejects_rotations() {
+    FourByFourState::solved().apply_move(&Move {
+        move_face: MoveFace::Right,
+        move_type: MoveType::Normal,
+        move_width: MoveWidth::Rotation,
+    });
+}
+
+#[test]
+fn test_4x4_random_states_are_solved() {
+    let mut rng = rand::rng();
+    for _ in 0..3 {
+        let mut state = FourByFourState::random(&mut rng);
+        let solution = state.solve();
+        state.apply_moves(&solution);
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_4x4_random_state_scramble_scrambles_the_cube() {
+    for seed in 0..3 {
+        let scramble = Scramble::generate_random_state_seeded(Cube::FourByFour, seed);
+        assert!(scramble.moves.len() <= 60);
+        assert!(scramble.moves.iter().all(|m| {
+            m.move_width == MoveWidth::Single
+                || [MoveFace::Right, MoveFace::Up, MoveFace::Front].contains(&m.move_face)
+        }));
+
+        let mut cubies = FourByFourState::solved();
+        cubies.apply_moves(&scramble.moves);
+        assert!(!cubies.is_solved());
+
+        // The sticker model checks the piece model, in whatever orientation
+        // the solution leaves the cube.
+        let mut state = CubeState::from_scramble(Cube::FourByFour, &scramble);
+        assert!(!state.is_solved());
+        state.apply_moves(&cubies.solve());
+        assert!(state.is_solved());
+    }
+}
+
+#[test]
+fn test_4x4_random_state_scrambles_average_about_54_moves() {
+    let lengths: Vec<usize> = (0..10)
+        .map(|seed| {
+            Scramble::generate_random_state_seeded(Cube::FourByFour, seed)
+                .moves
+                .len()
+        })
+        .collect();
+    let average = lengths.iter().sum::<usize>() as f64 / lengths.len() as f64;
+    assert!(average <= 55.0, "average length {average}");
+}
@@ -0,0 +1,106 @@
+use scramble_gen::puzzles::square1::{Square1Move, Square1Scramble, Square1State};
+
+fn turn(top: i8, bottom: i8) -> Square1Move {
+    Square1Move::Turn { top, bottom }
+}
+
+/// Applies the moves, checking before each slash that the slice only cuts
+/// between pieces. A corner takes up two neighbouring units, and the slice
+/// runs between units 11 and 0 and between units 5 and 6 of each layer.
+fn apply_checking_slashes(state: &mut Square1State, moves: &[Square1Move]) {
+    for move_ in moves {
+        if *move_ == Square1Move::Slash {
+            for layer in [state.top(), state.bottom()] {
+                assert_ne!(layer[11], layer[0], "corner split by the slice");
+                assert_ne!(layer[5], layer[6], "corner split by the slice");
+            }
+            assert!(state.is_twistable());
+        }
+        state.apply_move(move_);
+    }
+}
+
+#[test]
+fn test_move_display() {
+    assert_eq!(turn(1, 0).to_string(), "(1,0)");
+    assert_eq!(turn(-3, 6).to_string(), "(-3,6)");
+    assert_eq!(Square1Move::Slash.to_string(), "/");
+}
+
+#[test]
+fn test_inverse_keeps_half_turns_positive() {
+    assert_eq!(turn(6, -1).inverse(), turn(6, 1));
+    assert_eq!(turn(-5, 4).inverse(), turn(5, -4));
+    assert_eq!(Square1Move::Slash.inverse(), Square1Move::Slash);
+}
+
+#[test]
+fn test_two_slashes_are_identity() {
+    let mut state = Square1State::solved();
+    state.apply_move(&Square1Move::Slash);
+    assert!(!state.is_solved());
+    assert!(state.is_middle_flipped());
+    state.apply_move(&Square1Move::Slash);
+    assert!(state.is_solved());
+}
+
+#[test]
+fn test_slash_swaps_right_halves() {
+    let mut state = Square1State::solved();
+    state.apply_move(&Square1Move::Slash);
+    let solved = Square1State::solved();
+    assert_eq!(state.top()[..6], solved.top()[..6]);
+    assert_eq!(state.bottom()[..6], solved.bottom()[..6]);
+    assert_eq!(state.top()[6..], [15, 7, 7, 14, 6, 6]);
+}
+
+#[test]
+fn test_twistable_turns_from_solved() {
+    for (move_, twistable) in [
+        (turn(1, 0), true),
+        (turn(0, -1), true),
+        (turn(-1, 0), false),
+        (turn(0, 1), false),
+        (turn(3, 3), true),
+    ] {
+        let mut state = Square1State::solved();
+        state.apply_move(&move_);
+        assert_eq!(state.is_twistable(), twistable, "after {move_}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "a corner blocks the slash")]
+fn test_blocked_slash_panics() {
+    let mut state = Square1State::solved();
+    state.apply_move(&turn(-1, 0));
+    state.apply_move(&Square1Move::Slash);
+}
+
+#[test]
+fn test_random_state_scrambles() {
+    for seed in 0..10 {
+        let scramble = Square1Scramble::generate_seeded(seed);
+        for pair in scramble.moves.windows(2) {
+            assert!(!matches!(pair, [Square1Move::Slash, Square1Move::Slash]));
+            assert!(!matches!(
+                pair,
+                [Square1Move::Turn { .. }, Square1Move::Turn { .. }]
+            ));
+        }
+        for move_ in &scramble.moves {
+            if let Square1Move::Turn { top, bottom } = *move_ {
+                assert!((-5..=6).contains(&top) && (-5..=6).contains(&bottom));
+                assert!(top != 0 || bottom != 0);
+            }
+        }
+
+        let mut state = Square1State::solved();
+        apply_checking_slashes(&mut state, &scramble.moves);
+        assert!(!state.is_solved());
+
+        let solution = state.solve();
+        apply_checking_slashes(&mut state, &solution);
+        assert!(state.is_solved());
+    }
+}
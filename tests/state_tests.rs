@@ -0,0 +1,248 @@
+use scramble_gen::solvers::three::{DEFAULT_MAX_LENGTH, ThreeByThreeState};
+use scramble_gen::{Cube, CubeState, Move, MoveFace, MoveType, MoveWidth, Scramble};
+
+const CUBES: [Cube; 6] = [
+    Cube::TwoByTwo,
+    Cube::ThreeByThree,
+    Cube::FourByFour,
+    Cube::FiveByFive,
+    Cube::SixBySix,
+    Cube::SevenBySeven,
+];
+
+const FACES: [MoveFace; 6] = [
+    MoveFace::Up,
+    MoveFace::Right,
+    MoveFace::Front,
+    MoveFace::Down,
+    MoveFace::Left,
+    MoveFace::Back,
+];
+
+fn move_(move_face: MoveFace, move_type: MoveType, move_width: MoveWidth) -> Move {
+    Move {
+        move_face,
+        move_type,
+        move_width,
+    }
+}
+
+fn inverse(scramble: &Scramble) -> Vec<Move> {
+    scramble.moves.iter().rev().map(Move::inverse).collect()
+}
+
+#[test]
+fn test_new_state_is_solved() {
+    for cube in CUBES {
+        let state = CubeState::new(cube);
+        assert_eq!(state.size(), cube.layers());
+        assert!(state.is_solved());
+        for face in FACES {
+            assert!(state.face(face).iter().all(|&sticker| sticker == face));
+        }
+    }
+}
+
+#[test]
+fn test_four_quarter_turns_are_identity() {
+    for cube in CUBES {
+        for face in FACES {
+            for width in [MoveWidth::Single, MoveWidth::Wide, MoveWidth::ThreeWide] {
+                let mut state = CubeState::new(cube);
+                let turn = move_(face, MoveType::Normal, width);
+                state.apply_move(&turn);
+                // Turning every layer is a rotation, which leaves the cube solved.
+                if width.layers().unwrap() < cube.layers() {
+                    assert!(!state.is_solved(), "{turn} should scramble a {cube:?}");
+                }
+                for _ in 0..3 {
+                    state.apply_move(&turn);
+                }
+                assert_eq!(state, CubeState::new(cube), "{turn} on {cube:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_prime_undoes_normal() {
+    for face in FACES {
+        let mut state = CubeState::new(Cube::FiveByFive);
+        state.apply_move(&move_(face, MoveType::Normal, MoveWidth::Wide));
+        state.apply_move(&move_(face, MoveType::Prime, MoveWidth::Wide));
+        assert_eq!(state, CubeState::new(Cube::FiveByFive));
+    }
+}
+
+#[test]
+fn test_r_moves_front_to_up() {
+    let mut state = CubeState::new(Cube::ThreeByThree);
+    state.apply_move(&move_(MoveFace::Right, MoveType::Normal, MoveWidth::Single));
+
+    for row in 0..3 {
+        assert_eq!(state.sticker(MoveFace::Up, row, 2), MoveFace::Front);
+        assert_eq!(state.sticker(MoveFace::Up, row, 1), MoveFace::Up);
+        assert_eq!(state.sticker(MoveFace::Front, row, 2), MoveFace::Down);
+        assert_eq!(state.sticker(MoveFace::Back, row, 0), MoveFace::Up);
+        assert_eq!(state.sticker(MoveFace::Down, row, 2), MoveFace::Back);
+    }
+}
+
+#[test]
+fn test_u_moves_front_to_left() {
+    let mut state = CubeState::new(Cube::ThreeByThree);
+    state.apply_move(&move_(MoveFace::Up, MoveType::Normal, MoveWidth::Single));
+
+    for col in 0..3 {
+        assert_eq!(state.sticker(MoveFace::Left, 0, col), MoveFace::Front);
+        assert_eq!(state.sticker(MoveFace::Front, 0, col), MoveFace::Right);
+        assert_eq!(state.sticker(MoveFace::Front, 1, col), MoveFace::Front);
+    }
+}
+
+#[test]
+fn test_wide_move_turns_two_layers() {
+    let mut state = CubeState::new(Cube::FourByFour);
+    state.apply_move(&move_(MoveFace::Right, MoveType::Normal, MoveWidth::Wide));
+
+    for row in 0..4 {
+        assert_eq!(state.sticker(MoveFace::Up, row, 3), MoveFace::Front);
+        assert_eq!(state.sticker(MoveFace::Up, row, 2), MoveFace::Front);
+        assert_eq!(state.sticker(MoveFace::Up, row, 1), MoveFace::Up);
+    }
+}
+
+#[test]
+fn test_opposite_wide_moves_are_a_rotation() {
+    let mut state = CubeState::new(Cube::FourByFour);
+    state.apply_moves(&[
+        move_(MoveFace::Right, MoveType::Normal, MoveWidth::Wide),
+        move_(MoveFace::Left, MoveType::Prime, MoveWidth::Wide),
+    ]);
+    assert!(state.is_solved());
+    assert_ne!(state, CubeState::new(Cube::FourByFour));
+}
+
+#[test]
+fn test_big_cube_layers_move() {
+    let mut state = CubeState::new(Cube::NxN(9));
+    state.apply_move(&move_(
+        MoveFace::Right,
+        MoveType::Normal,
+        MoveWidth::Layers(4),
+    ));
+    for col in 0..9 {
+        let expected = if col >= 5 {
+            MoveFace::Front
+        } else {
+            MoveFace::Up
+        };
+        assert_eq!(state.sticker(MoveFace::Up, 0, col), expected);
+    }
+}
+
+fn state_after(cube: Cube, moves: &str) -> CubeState {
+    CubeState::from_scramble(cube, &moves.parse().unwrap())
+}
+
+#[test]
+fn test_rotations_turn_every_layer() {
+    for (rotation, layers) in [("x", "R M' L'"), ("y", "U E' D'"), ("z", "F S B'")] {
+        assert_eq!(
+            state_after(Cube::ThreeByThree, rotation),
+            state_after(Cube::ThreeByThree, layers),
+            "{rotation}"
+        );
+    }
+    for cube in CUBES {
+        let state = state_after(cube, "x y' z2");
+        assert!(state.is_solved());
+        assert_ne!(state, CubeState::new(cube));
+    }
+}
+
+#[test]
+fn test_middle_layers() {
+    assert_eq!(
+        state_after(Cube::FiveByFive, "M"),
+        state_after(Cube::FiveByFive, "3-3L")
+    );
+    // An even cube has two middle layers.
+    assert_eq!(
+        state_after(Cube::FourByFour, "E'"),
+        state_after(Cube::FourByFour, "2-2D' 3-3D'")
+    );
+}
+
+#[test]
+fn test_inner_slice_turns_one_layer() {
+    assert_eq!(
+        state_after(Cube::FiveByFive, "2-2R"),
+        state_after(Cube::FiveByFive, "Rw R'")
+    );
+
+    // The third layer from F is the second row of U, counted from the back.
+    let state = state_after(Cube::FourByFour, "3-3F2");
+    for row in 0..4 {
+        let expected = if row == 1 {
+            MoveFace::Down
+        } else {
+            MoveFace::Up
+        };
+        for col in 0..4 {
+            assert_eq!(state.sticker(MoveFace::Up, row, col), expected);
+        }
+    }
+}
+
+#[test]
+fn test_sexy_move_has_order_six() {
+    let sexy = [
+        move_(MoveFace::Right, MoveType::Normal, MoveWidth::Single),
+        move_(MoveFace::Up, MoveType::Normal, MoveWidth::Single),
+        move_(MoveFace::Right, MoveType::Prime, MoveWidth::Single),
+        move_(MoveFace::Up, MoveType::Prime, MoveWidth::Single),
+    ];
+    let mut state = CubeState::new(Cube::ThreeByThree);
+    for i in 1..=6 {
+        state.apply_moves(&sexy);
+        assert_eq!(state.is_solved(), i == 6);
+    }
+}
+
+#[test]
+fn test_scramble_followed_by_inverse_is_solved() {
+    for cube in CUBES {
+        let scramble = Scramble::generate(cube, None);
+        let mut state = CubeState::from_scramble(cube, &scramble);
+        assert!(
+            !state.is_solved(),
+            "{cube:?} scramble {scramble} is a no-op"
+        );
+        state.apply_moves(&inverse(&scramble));
+        assert_eq!(state, CubeState::new(cube));
+    }
+}
+
+#[test]
+fn test_nxn_scramble_followed_by_inverse_is_solved() {
+    let cube = Cube::NxN(11);
+    let scramble = Scramble::generate(cube, None);
+    let mut state = CubeState::from_scramble(cube, &scramble);
+    assert_eq!(state.size(), 11);
+    assert!(!state.is_solved());
+    state.apply_moves(&inverse(&scramble));
+    assert_eq!(state, CubeState::new(cube));
+}
+
+#[test]
+fn test_random_state_solution_solves_facelets() {
+    let scramble = Scramble::generate_random_state(Cube::ThreeByThree);
+    let mut cubies = ThreeByThreeState::solved();
+    cubies.apply_moves(&scramble.moves);
+    let solution = cubies.solve(DEFAULT_MAX_LENGTH).unwrap();
+
+    let mut state = CubeState::from_scramble(Cube::ThreeByThree, &scramble);
+    state.apply_moves(&solution);
+    assert_eq!(state, CubeState::new(Cube::ThreeByThree));
+}
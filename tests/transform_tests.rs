@@ -0,0 +1,80 @@
+use rstest::rstest;
+use scramble_gen::transform::MirrorPlane;
+use scramble_gen::{Cube, CubeState, Scramble};
+
+fn scramble(moves: &str) -> Scramble {
+    moves.parse().unwrap()
+}
+
+#[test]
+fn test_inverse_reverses_and_inverts() {
+    assert_eq!(scramble("R U' Fw2 x").inverse(), scramble("x' Fw2 U R'"));
+}
+
+#[test]
+fn test_inverse_undoes_scramble() {
+    for cube in [Cube::ThreeByThree, Cube::FiveByFive] {
+        let scramble = Scramble::generate_seeded(cube, None, 5);
+        let mut state = CubeState::from_scramble(cube, &scramble);
+        state.apply_moves(&scramble.inverse().moves);
+        assert_eq!(state, CubeState::new(cube));
+    }
+}
+
+#[rstest]
+#[case(MirrorPlane::M, "R U F' 2-2L M x", "L' U' F 2-2R' M x")]
+#[case(MirrorPlane::E, "R U F' 3Dw E' y", "R' D' F 3Uw' E' y")]
+#[case(MirrorPlane::S, "R U F' Bw2 S z'", "R' U' B Fw2 S z'")]
+fn test_mirror(#[case] plane: MirrorPlane, #[case] input: &str, #[case] expected: &str) {
+    let mirrored = scramble(input).mirror(plane);
+    assert_eq!(mirrored, scramble(expected));
+    assert_eq!(mirrored.mirror(plane), scramble(input));
+}
+
+#[test]
+fn test_mirror_reflects_the_state() {
+    // A mirrored scramble solves the mirrored cube, so its inverse does too.
+    let scramble = Scramble::generate_seeded(Cube::ThreeByThree, None, 2);
+    let mut state = CubeState::from_scramble(Cube::ThreeByThree, &scramble.mirror(MirrorPlane::M));
+    assert!(!state.is_solved());
+    state.apply_moves(&scramble.inverse().mirror(MirrorPlane::M).moves);
+    assert!(state.is_solved());
+}
+
+#[rstest]
+#[case("z2", "R U F2", "L D F2")]
+#[case("x2", "R U F'", "R D B'")]
+#[case("x", "U M E", "F M S'")]
+#[case("y", "R Rw x", "B Bw z'")]
+fn test_rotate(#[case] rotation: &str, #[case] input: &str, #[case] expected: &str) {
+    assert_eq!(
+        scramble(input).rotate(&scramble(rotation).moves),
+        scramble(expected)
+    );
+}
+
+#[rstest]
+#[case("x")]
+#[case("y'")]
+#[case("z2")]
+#[case("x y")]
+#[case("z' x2 y'")]
+fn test_rotate_matches_turning_the_cube(#[case] rotation: &str) {
+    let rotation = scramble(rotation);
+    for cube in [Cube::ThreeByThree, Cube::FourByFour] {
+        let scramble = Scramble::generate_seeded(cube, None, 9);
+        let mut held = CubeState::new(cube);
+        held.apply_moves(&rotation.moves);
+        held.apply_moves(&scramble.moves);
+        held.apply_moves(&rotation.inverse().moves);
+
+        let rotated = scramble.rotate(&rotation.moves);
+        assert_eq!(CubeState::from_scramble(cube, &rotated), held);
+    }
+}
+
+#[test]
+#[should_panic(expected = "R is not a rotation")]
+fn test_rotate_rejects_face_turns() {
+    scramble("U").rotate(&scramble("R").moves);
+}